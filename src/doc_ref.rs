@@ -126,12 +126,26 @@ impl<'a, T> DocRef<'a, T> {
     pub(crate) fn get(&self, id: &Id) -> Option<DocRef<'a, Item>> {
         self.crate_docs.get(self.request, id)
     }
+
+    /// Get an item by an id from this crate's docs, loading the crate it belongs to if that is
+    /// another one, as with the source of a re-export
+    pub(crate) fn get_or_external(&self, id: &Id) -> Option<DocRef<'a, Item>> {
+        self.get(id)
+            .or_else(|| self.request.get_external(self.crate_docs, id))
+    }
 }
 
 impl<'a> DocRef<'a, Use> {
     pub(crate) fn name(self) -> &'a str {
         self.name.unwrap_or(&self.item.name)
     }
+
+    /// The item this `use` imports, in this crate or another
+    pub(crate) fn source_item(self) -> Option<DocRef<'a, Item>> {
+        self.id
+            .and_then(|id| self.get_or_external(&id))
+            .or_else(|| self.request.resolve_path(&self.source, &mut vec![]))
+    }
 }
 
 #[derive(Debug)]
@@ -143,6 +157,13 @@ impl<'a> From<&'a ItemSummary> for Path<'a> {
     }
 }
 
+impl<'a> Path<'a> {
+    /// The path's segments, starting with the crate's name
+    pub(crate) fn segments(&self) -> &'a [String] {
+        self.0
+    }
+}
+
 impl Display for Path<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         for (i, segment) in self.0.iter().enumerate() {
@@ -2,7 +2,11 @@ use fieldwork::Fieldwork;
 use rustdoc_types::ItemKind;
 use strum::VariantArray;
 
-use crate::{filter::Filter, tools::GetItem, verbosity::Verbosity};
+use crate::request::{Bindings, TypeExpr};
+use crate::{filter::Filter, pagination::Page, tools::GetItem, verbosity::Verbosity};
+use anyhow::Result;
+use rustc_hash::FxHashMap;
+use rustdoc_types::Id;
 
 /// Context for formatting operations
 #[derive(Debug, Clone, Fieldwork)]
@@ -13,11 +17,26 @@ pub(crate) struct FormatContext {
     /// Whether to show recursive/nested content
     #[field = "is_recursive"]
     recursive: bool,
+    /// Whether to show trait implementations in full rather than by name
+    impl_details: bool,
     /// Level of documentation detail to show
     #[field(copy)]
     verbosity: Verbosity,
     /// Filter items by type
     filters: Vec<Filter>,
+    /// Output budget and starting offset for listings
+    #[field(copy)]
+    page: Page,
+    /// Resource URI of the item being shown, so that responses can cite it
+    #[field(with)]
+    uri: Option<String>,
+    /// The type with generic arguments the item is shown for, like `Option<&str>`
+    #[field(with)]
+    concrete_type: Option<TypeExpr>,
+    /// What the type parameters of each method's impl stand for, by method, when showing a
+    /// concrete type
+    #[field(with)]
+    substitutions: FxHashMap<Id, Bindings>,
 }
 
 impl Default for FormatContext {
@@ -25,21 +44,31 @@ impl Default for FormatContext {
         Self {
             include_source: false,
             recursive: false,
+            impl_details: false,
             verbosity: Verbosity::Brief,
             filters: Filter::VARIANTS.into(),
+            page: Page::default(),
+            uri: None,
+            concrete_type: None,
+            substitutions: FxHashMap::default(),
         }
     }
 }
 
 impl FormatContext {
     /// Create context from GetItem tool arguments
-    pub(crate) fn from_get_item(item: &GetItem) -> Self {
-        Self {
+    pub(crate) fn from_get_item(item: &GetItem) -> Result<Self> {
+        Ok(Self {
             include_source: item.include_source(),
             recursive: item.recursive(),
+            impl_details: item.impl_details(),
             verbosity: item.verbosity(),
             filters: item.filters().to_vec(),
-        }
+            page: item.page()?,
+            uri: None,
+            concrete_type: item.concrete_type(),
+            substitutions: FxHashMap::default(),
+        })
     }
 
     pub(crate) fn filter_match_kind(&self, kind: ItemKind) -> bool {
@@ -0,0 +1,145 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// A lock on a file that other processes take too, held until dropped
+///
+/// The lock is advisory: it only excludes others that take the same lock. It is either
+/// exclusive, or shared with others taking it shared.
+#[derive(Debug)]
+pub(crate) struct FileLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// Block until the lock at `path` is held, creating the lock file and its directory if needed
+    pub(crate) fn acquire(path: &Path) -> io::Result<Self> {
+        Self::open(path, false)
+    }
+
+    /// Block until the lock at `path` is held shared, as by readers of what exclusive holders
+    /// write
+    pub(crate) fn acquire_shared(path: &Path) -> io::Result<Self> {
+        Self::open(path, true)
+    }
+
+    fn open(path: &Path, shared: bool) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        if shared {
+            file.lock_shared()?;
+        } else {
+            file.lock()?;
+        }
+        log::trace!("locked {}", path.display());
+        Ok(Self {
+            file,
+            path: path.to_owned(),
+        })
+    }
+
+    /// Take the lock if possible, logging and continuing without it otherwise
+    ///
+    /// For work that is still correct without the lock, only slower or duplicated.
+    pub(crate) fn acquire_or_warn(path: &Path) -> Option<Self> {
+        Self::acquire(path)
+            .inspect_err(|error| log::warn!("could not lock {}: {error}", path.display()))
+            .ok()
+    }
+
+    /// Take the lock shared if possible, logging and continuing without it otherwise
+    pub(crate) fn acquire_shared_or_warn(path: &Path) -> Option<Self> {
+        Self::acquire_shared(path)
+            .inspect_err(|error| log::warn!("could not lock {}: {error}", path.display()))
+            .ok()
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+        log::trace!("unlocked {}", self.path.display());
+    }
+}
+
+/// Write a file so that readers see either its previous contents or all of the new contents
+///
+/// The contents go to a temporary file in the same directory, which is renamed over `path` once
+/// complete. If `write` fails, the temporary file is removed and `path` is untouched.
+pub(crate) fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = File::create(&temp_path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        fs::rename(&temp_path, path)
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_writes_leave_the_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data");
+
+        write_atomically(&path, |writer| writer.write_all(b"first")).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"first");
+
+        let error = write_atomically(&path, |writer| {
+            writer.write_all(b"partial")?;
+            Err(io::Error::other("interrupted"))
+        })
+        .unwrap_err();
+        assert_eq!(error.to_string(), "interrupted");
+        assert_eq!(fs::read(&path).unwrap(), b"first");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn locks_exclude_each_other() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("lock");
+
+        let lock = FileLock::acquire(&path).unwrap();
+        let other = File::open(&path).unwrap();
+        assert!(other.try_lock().is_err());
+        drop(lock);
+        assert!(other.try_lock().is_ok());
+    }
+
+    #[test]
+    fn shared_locks_exclude_only_exclusive_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lock");
+
+        let reader = FileLock::acquire_shared(&path).unwrap();
+        let other = File::open(&path).unwrap();
+        assert!(other.try_lock_shared().is_ok());
+        other.unlock().unwrap();
+        assert!(other.try_lock().is_err());
+        drop(reader);
+        assert!(other.try_lock().is_ok());
+    }
+}
@@ -1,39 +1,167 @@
+use anyhow::{Result, anyhow, bail};
 use bincode::{Decode, Encode, config};
 use fieldwork::Fieldwork;
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHasher;
-use rustdoc_types::{Item, ItemEnum, StructKind, Trait};
-use std::collections::BTreeMap;
+use rustdoc_types::{
+    GenericArg, GenericArgs, GenericBound, Item, ItemEnum, StructKind, Trait, Type,
+};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
-use std::fs::OpenOptions;
 use std::hash::{Hash, Hasher};
+use std::io::{self, BufReader};
 use std::path::Path;
 use std::time::SystemTime;
 
+mod analyzer;
+mod names;
+mod stemmer;
+
 use crate::{
     doc_ref::DocRef,
+    fs_utils::{FileLock, write_atomically},
     request::{Request, Suggestion},
 };
+use analyzer::{Token, analyze, analyze_names, is_stopword};
+pub(crate) use names::NameIndex;
+
+/// A part of an item's documentation that is scored separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// The item's own name
+    Name,
+    /// Names of the modules and types the item is nested in
+    Path,
+    /// The first paragraph of the docs
+    Summary,
+    /// The rest of the docs, and the names and docs of fields and trait members
+    Body,
+    /// Type names in the item's signature: parameters, return type and field types
+    Signature,
+}
+
+impl Field {
+    const COUNT: usize = 5;
+}
+
+/// Environment variable that overrides some of the [`FieldWeights`], like `name=6,b=0.5`
+const WEIGHTS_ENV: &str = "RUSTDOC_MCP_SEARCH_WEIGHTS";
+
+/// How much a match in each field counts towards an item's score, along with the BM25
+/// parameters
+///
+/// Any of them can be set through [`WEIGHTS_ENV`]. Scores are computed when the index is
+/// built, so indexes built with other weights are rebuilt rather than reused.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub(crate) struct FieldWeights {
+    pub(crate) name: f32,
+    pub(crate) path: f32,
+    pub(crate) summary: f32,
+    pub(crate) body: f32,
+    pub(crate) signature: f32,
+    /// Term frequency saturation: how quickly repeated matches stop adding to the score
+    pub(crate) k1: f32,
+    /// Length normalization: 0 ignores field length, 1 fully normalizes by it
+    pub(crate) b: f32,
+}
+
+impl Default for FieldWeights {
+    fn default() -> Self {
+        Self {
+            name: 4.0,
+            path: 1.0,
+            summary: 1.5,
+            body: 0.5,
+            signature: 1.0,
+            k1: 1.2,
+            b: 0.75,
+        }
+    }
+}
+
+impl FieldWeights {
+    /// The defaults, with any overrides from [`WEIGHTS_ENV`]
+    pub(crate) fn configured() -> Result<Self> {
+        match std::env::var(WEIGHTS_ENV) {
+            Ok(overrides) => Self::default()
+                .with_overrides(&overrides)
+                .map_err(|error| anyhow!("invalid {WEIGHTS_ENV}: {error}")),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Apply comma-separated `field=weight` pairs, like `name=6,body=0.25`
+    fn with_overrides(mut self, overrides: &str) -> Result<Self> {
+        for pair in overrides
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (field, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow!("expected `field=weight`, found `{pair}`"))?;
+            let value = value.trim();
+            let value = value
+                .parse::<f32>()
+                .ok()
+                .filter(|value| value.is_finite() && *value >= 0.0)
+                .ok_or_else(|| anyhow!("`{value}` is not a non-negative number"))?;
+            let weight = match field.trim() {
+                "name" => &mut self.name,
+                "path" => &mut self.path,
+                "summary" => &mut self.summary,
+                "body" => &mut self.body,
+                "signature" => &mut self.signature,
+                "k1" => &mut self.k1,
+                "b" => &mut self.b,
+                other => bail!(
+                    "unknown weight `{other}`, expected name, path, summary, body, signature, k1 \
+                     or b"
+                ),
+            };
+            *weight = value;
+        }
+        Ok(self)
+    }
+
+    fn weight(&self, field: Field) -> f32 {
+        match field {
+            Field::Name => self.name,
+            Field::Path => self.path,
+            Field::Summary => self.summary,
+            Field::Body => self.body,
+            Field::Signature => self.signature,
+        }
+    }
+}
 
 #[derive(Default, Debug, Clone, Fieldwork)]
 struct Terms<'a> {
-    term_docs: BTreeMap<u64, BTreeMap<(u64, u32), f32>>,
+    /// Occurrences of each term in each field of each item
+    term_docs: BTreeMap<u64, BTreeMap<(u64, u32), [f32; Field::COUNT]>>,
+    /// Number of terms in each field of each item
+    field_lengths: BTreeMap<(u64, u32), [f32; Field::COUNT]>,
     shortest_paths: BTreeMap<(u64, u32), Vec<u32>>,
     crate_hashes: FxHashMap<&'a str, u64>,
+    /// The crate being indexed, as opposed to those it re-exports items from
+    own_crate: Option<u64>,
+    /// Every surface form seen, with the hash of the term it was indexed under
+    vocabulary: BTreeSet<(String, u64)>,
 }
 
 impl<'a> Terms<'a> {
-    fn add(&mut self, word: &str, tf_score: f32, id: (u64, u32)) {
-        let term_hash = hash_term(word);
-        *self
-            .term_docs
+    fn add(&mut self, token: Token, field: Field, id: (u64, u32)) {
+        let term_hash = hash_term(&token.term);
+        self.vocabulary.insert((token.surface, term_hash));
+        self.term_docs
             .entry(term_hash)
             .or_default()
             .entry(id)
-            .or_default() += tf_score;
+            .or_default()[field as usize] += 1.0;
     }
 
-    fn finalize(self) -> SearchableTerms {
+    /// Score every term for every item with BM25F
+    fn finalize(self, weights: FieldWeights) -> SearchableTerms {
         let total_docs = self.shortest_paths.len() as f32;
         let mut ids = vec![];
 
@@ -44,35 +172,92 @@ impl<'a> Terms<'a> {
             ids.push(id_path);
         }
 
+        // Lengths are judged against the crate's own items, so that re-exporting a type with
+        // long docs, like `Vec`, doesn't make every other item's docs look short
+        let own_lengths = self
+            .field_lengths
+            .iter()
+            .filter(|((crate_hash, _), _)| self.own_crate.is_none_or(|own| *crate_hash == own))
+            .map(|(_, lengths)| lengths)
+            .collect::<Vec<_>>();
+        let mut average_lengths = [0.0; Field::COUNT];
+        for lengths in &own_lengths {
+            for (average, length) in average_lengths.iter_mut().zip(*lengths) {
+                *average += length / own_lengths.len() as f32;
+            }
+        }
+
+        let fields = [
+            Field::Name,
+            Field::Path,
+            Field::Summary,
+            Field::Body,
+            Field::Signature,
+        ];
+        let no_lengths = [0.0; Field::COUNT];
+
         let terms = self
             .term_docs
             .into_iter()
-            .map(|(term_hash, doc_scores)| {
-                // Calculate IDF for this term
-                let doc_freq = doc_scores.len() as f32;
-                let idf = (total_docs / doc_freq).ln();
-
-                // Apply TF-IDF scoring
-                let mut tf_idf_scores: Vec<_> = doc_scores
+            .map(|(term_hash, doc_counts)| {
+                // Items nested in another share its name as their path, which shouldn't make
+                // that name less distinctive, so only other fields count towards frequency
+                let own_freq = doc_counts
+                    .values()
+                    .filter(|counts| {
+                        fields
+                            .iter()
+                            .any(|&field| field != Field::Path && counts[field as usize] > 0.0)
+                    })
+                    .count();
+                let doc_freq = match own_freq {
+                    0 => doc_counts.len(),
+                    own_freq => own_freq,
+                } as f32;
+                let idf = (1.0 + (total_docs - doc_freq + 0.5) / (doc_freq + 0.5)).ln();
+
+                let mut scores: Vec<_> = doc_counts
                     .into_iter()
-                    .filter_map(|(doc_id, tf_score)| {
-                        id_set
-                            .get(&doc_id)
-                            .map(|id| (*id, (1.0 + tf_score.ln()) * idf))
+                    .filter_map(|(doc_id, counts)| {
+                        let lengths = self.field_lengths.get(&doc_id).unwrap_or(&no_lengths);
+                        // Combine fields into one weighted, length-normalized frequency before
+                        // saturating, so that a term repeated across fields is not over-counted
+                        let frequency: f32 = fields
+                            .iter()
+                            .map(|&field| {
+                                let field_index = field as usize;
+                                let average = average_lengths[field_index];
+                                let normalization = if average > 0.0 {
+                                    1.0 - weights.b + weights.b * lengths[field_index] / average
+                                } else {
+                                    1.0
+                                };
+                                weights.weight(field) * counts[field_index] / normalization
+                            })
+                            .sum();
+                        let score = idf * frequency * (weights.k1 + 1.0) / (weights.k1 + frequency);
+                        id_set.get(&doc_id).map(|id| (*id, score))
                     })
                     .collect();
 
-                // Sort by TF-IDF score (descending)
-                tf_idf_scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+                scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
 
-                (term_hash, tf_idf_scores)
+                (term_hash, scores)
             })
             .collect();
 
-        SearchableTerms { terms, ids }
+        SearchableTerms {
+            terms,
+            ids,
+            vocabulary: self.vocabulary.into_iter().collect(),
+            weights,
+        }
     }
 
-    fn recurse(&mut self, item: DocRef<'a, Item>, ids: &[u32], add_id: bool) {
+    /// Index an item and everything below it
+    ///
+    /// `path` holds the names of the modules and types the item is nested in.
+    fn recurse(&mut self, item: DocRef<'a, Item>, ids: &[u32], path: &[&'a str], add_id: bool) {
         let mut ids = ids.to_owned();
         if add_id {
             ids.push(item.id.0);
@@ -83,6 +268,7 @@ impl<'a> Terms<'a> {
             .crate_hashes
             .entry(crate_name)
             .or_insert_with(|| hash_term(crate_name));
+        self.own_crate.get_or_insert(crate_hash);
 
         let id = (crate_hash, *ids.last().unwrap_or(&item.id.0));
 
@@ -93,70 +279,191 @@ impl<'a> Terms<'a> {
             return;
         }
 
-        self.add_for_item(item, id);
+        self.add_for_item(item, id, path);
 
         match item.inner() {
             ItemEnum::Struct(struct_item) => match &struct_item.kind {
                 StructKind::Unit => {}
                 StructKind::Tuple(field_ids) => {
                     for field in field_ids.iter().flatten().filter_map(|id| item.get(id)) {
-                        self.add_for_item(field, id);
+                        self.add_member(field, id);
                     }
                 }
                 StructKind::Plain { fields, .. } => {
                     for field in item.id_iter(fields) {
-                        self.add_for_item(field, id);
+                        self.add_member(field, id);
                     }
                 }
             },
             ItemEnum::Trait(Trait { items, .. }) => {
-                for field in item.id_iter(items) {
-                    self.recurse(field, &ids, false);
+                for trait_item in item.id_iter(items) {
+                    self.add_member(trait_item, id);
                 }
             }
             _ => {}
         };
 
+        let mut child_path = path.to_vec();
+        if add_id && let Some(name) = item.name() {
+            child_path.push(name);
+        }
         for child in item.child_items().with_use() {
-            self.recurse(child, &ids, true)
+            self.recurse(child, &ids, &child_path, true)
         }
 
         self.shortest_paths.insert(id, ids);
     }
 
-    fn add_for_item(&mut self, item: DocRef<'a, Item>, id: (u64, u32)) {
+    fn add_for_item(&mut self, item: DocRef<'a, Item>, id: (u64, u32), path: &[&str]) {
         if let Some(name) = item.name() {
-            self.add_terms(name, id, 2.0);
+            self.add_terms(name, id, Field::Name);
+        }
+
+        for segment in path {
+            self.add_terms(segment, id, Field::Path);
         }
 
         if let Some(docs) = &item.docs {
-            self.add_terms(docs, id, 1.0);
+            let (summary, body) = docs.split_once("\n\n").unwrap_or((docs, ""));
+            self.add_terms(summary, id, Field::Summary);
+            self.add_terms(body, id, Field::Body);
         }
+
+        self.add_signature(item, id);
     }
 
-    fn add_terms(&mut self, text: &str, id: (u64, u32), base_score: f32) {
-        let words = tokenize(text);
+    /// Index a struct field or trait member as part of the item that contains it
+    fn add_member(&mut self, member: DocRef<'a, Item>, id: (u64, u32)) {
+        if let Some(name) = member.name() {
+            self.add_terms(name, id, Field::Body);
+        }
 
-        // Count word frequencies in this document
-        let mut word_counts: BTreeMap<&str, usize> = BTreeMap::new();
-        for word in &words {
-            *word_counts.entry(word).or_insert(0) += 1;
+        if let Some(docs) = &member.docs {
+            self.add_terms(docs, id, Field::Body);
         }
 
-        // Add each unique word to the index
-        for (word, count) in word_counts {
-            // Simple relevance scoring: term frequency / document length * base score
-            let tf_score = (count as f32) * base_score;
+        self.add_signature(member, id);
+    }
 
-            self.add(word, tf_score, id);
+    fn add_signature(&mut self, item: DocRef<'a, Item>, id: (u64, u32)) {
+        let mut names = vec![];
+        match item.inner() {
+            ItemEnum::Function(function) => {
+                for (_, type_) in &function.sig.inputs {
+                    type_names(type_, &mut names);
+                }
+                if let Some(output) = &function.sig.output {
+                    type_names(output, &mut names);
+                }
+            }
+            ItemEnum::StructField(type_) => type_names(type_, &mut names),
+            _ => {}
+        }
+
+        for name in names {
+            self.add_terms(name, id, Field::Signature);
+        }
+    }
+
+    fn add_terms(&mut self, text: &str, id: (u64, u32), field: Field) {
+        let tokens = match field {
+            Field::Summary | Field::Body => analyze(text),
+            Field::Name | Field::Path | Field::Signature => analyze_names(text),
+        };
+        self.field_lengths.entry(id).or_default()[field as usize] += tokens.len() as f32;
+
+        for token in tokens {
+            self.add(token, field, id);
         }
     }
 }
 
+/// Collect the names of the types that make up a type, e.g. `Result` and `String` for
+/// `Result<String, Box<dyn Error>>` along with `Box` and `Error`
+fn type_names<'a>(type_: &'a Type, names: &mut Vec<&'a str>) {
+    match type_ {
+        Type::ResolvedPath(path) => path_names(path, names),
+        Type::DynTrait(dyn_trait) => {
+            for poly_trait in &dyn_trait.traits {
+                path_names(&poly_trait.trait_, names);
+            }
+        }
+        Type::ImplTrait(bounds) => {
+            for bound in bounds {
+                if let GenericBound::TraitBound { trait_, .. } = bound {
+                    path_names(trait_, names);
+                }
+            }
+        }
+        Type::Generic(name) | Type::Primitive(name) => names.push(name),
+        Type::Tuple(types) => {
+            for type_ in types {
+                type_names(type_, names);
+            }
+        }
+        Type::Slice(type_)
+        | Type::Array { type_, .. }
+        | Type::Pat { type_, .. }
+        | Type::RawPointer { type_, .. }
+        | Type::BorrowedRef { type_, .. } => type_names(type_, names),
+        Type::QualifiedPath { name, .. } => names.push(name),
+        Type::FunctionPointer(_) | Type::Infer => {}
+    }
+}
+
+fn path_names<'a>(path: &'a rustdoc_types::Path, names: &mut Vec<&'a str>) {
+    names.push(path.path.rsplit("::").next().unwrap_or(&path.path));
+    match path.args.as_deref() {
+        Some(GenericArgs::AngleBracketed { args, .. }) => {
+            for arg in args {
+                if let GenericArg::Type(type_) = arg {
+                    type_names(type_, names);
+                }
+            }
+        }
+        Some(GenericArgs::Parenthesized { inputs, output }) => {
+            for type_ in inputs.iter().chain(output) {
+                type_names(type_, names);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[derive(Debug, Clone, Encode, Decode, Fieldwork)]
 struct SearchableTerms {
     terms: BTreeMap<u64, Vec<(usize, f32)>>,
     ids: Vec<Vec<u32>>,
+    /// Every indexed surface form, sorted, with the hash of its term, for prefix and fuzzy
+    /// matching
+    vocabulary: Vec<(String, u64)>,
+    /// The weights scores were computed with
+    weights: FieldWeights,
+}
+
+/// Identifies a search index file, before the format and tool versions in its header
+const INDEX_MAGIC: [u8; 8] = *b"RDMCPIDX";
+
+/// Incremented whenever the encoding of [`SearchableTerms`] or the analysis of text changes
+const INDEX_FORMAT_VERSION: u32 = 2;
+
+/// The start of every index file, so that files written by any other version of this tool are
+/// rebuilt instead of being misread
+#[derive(Debug, Encode, Decode, PartialEq)]
+struct IndexHeader {
+    magic: [u8; 8],
+    format_version: u32,
+    tool_version: String,
+}
+
+impl IndexHeader {
+    fn current() -> Self {
+        Self {
+            magic: INDEX_MAGIC,
+            format_version: INDEX_FORMAT_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
 }
 
 /// A search index for a single crate
@@ -167,16 +474,55 @@ pub(crate) struct SearchIndex {
     terms: SearchableTerms,
 }
 
+/// Weight of a term that the query term is a prefix of, relative to an exact match
+const PREFIX_WEIGHT: f32 = 0.5;
+
+/// Weight of a term within edit distance of the query term, relative to an exact match
+const FUZZY_WEIGHT: f32 = 0.25;
+
+/// Most inexact terms a single query term expands to, closest first
+const MAX_EXPANSIONS: usize = 16;
+
+/// How many edits a query term of this length may be from an indexed term
+fn max_edits(len: usize) -> usize {
+    match len {
+        0..5 => 0,
+        5..9 => 1,
+        _ => 2,
+    }
+}
+
 impl SearchableTerms {
     fn search(&self, term: &str) -> Vec<(&[u32], f32)> {
         let mut results = BTreeMap::<usize, f32>::new();
-        for term in tokenize(term)
-            .into_iter()
-            .map(hash_term)
-            .filter_map(|term| self.terms.get(&term))
-        {
-            for (id, score) in term {
-                *results.entry(*id).or_default() += score;
+        // a stopword is only looked for if it is indexed, which means some name contains it
+        let query_tokens = analyze_names(term).into_iter().filter(|token| {
+            !is_stopword(&token.surface) || self.terms.contains_key(&hash_term(&token.term))
+        });
+        for query_token in query_tokens {
+            // Each item counts only its best match for a query term, so that an item containing
+            // several spellings close to it does not outscore one containing it exactly
+            let mut term_results = BTreeMap::<usize, f32>::new();
+            let exact_hash = hash_term(&query_token.term);
+            let exact = self.terms.get(&exact_hash);
+            for (id, score) in exact.into_iter().flatten() {
+                term_results.insert(*id, *score);
+            }
+
+            // An inexact match never outscores an exact one for the same query term
+            let floor = exact
+                .and_then(|postings| postings.last())
+                .map_or(f32::INFINITY, |(_, score)| *score);
+
+            for (term_hash, weight) in self.expansions(&query_token.surface, exact_hash) {
+                for (id, score) in self.terms.get(&term_hash).into_iter().flatten() {
+                    let best = term_results.entry(*id).or_default();
+                    *best = best.max(score.min(floor) * weight);
+                }
+            }
+
+            for (id, score) in term_results {
+                *results.entry(id).or_default() += score;
             }
         }
 
@@ -187,19 +533,97 @@ impl SearchableTerms {
         results.sort_by(|(_, a), (_, b)| b.total_cmp(a));
         results
     }
+
+    /// Hashes of indexed terms that a query word may have been meant as, with their weights
+    ///
+    /// These are the terms of surface forms the word is a proper prefix of, and of surface
+    /// forms within a few edits of it, excluding the word's own term.
+    fn expansions(&self, query_word: &str, exact_hash: u64) -> Vec<(u64, f32)> {
+        let start = self
+            .vocabulary
+            .partition_point(|(surface, _)| surface.as_str() <= query_word);
+        let prefixed = self.vocabulary[start..]
+            .iter()
+            .take_while(|(surface, _)| surface.starts_with(query_word))
+            .map(|(surface, term_hash)| {
+                let closeness = query_word.len() as f32 / surface.len() as f32;
+                (*term_hash, PREFIX_WEIGHT * closeness)
+            })
+            .collect::<Vec<_>>();
+
+        let max_edits = max_edits(query_word.chars().count());
+        let mut fuzzy = vec![];
+        if max_edits > 0 {
+            let len = query_word.len();
+            for (surface, term_hash) in &self.vocabulary {
+                if surface.len().abs_diff(len) > max_edits || surface.starts_with(query_word) {
+                    continue;
+                }
+                let edits = strsim::osa_distance(query_word, surface);
+                if edits <= max_edits {
+                    let closeness = 1.0 - edits as f32 / len as f32;
+                    fuzzy.push((*term_hash, FUZZY_WEIGHT * closeness));
+                }
+            }
+        }
+
+        let mut expansions = FxHashMap::<u64, f32>::default();
+        let prefixed = dedup_by_weight(prefixed, exact_hash);
+        for (term_hash, weight) in prefixed
+            .into_iter()
+            .chain(dedup_by_weight(fuzzy, exact_hash))
+        {
+            let best = expansions.entry(term_hash).or_default();
+            *best = best.max(weight);
+        }
+        expansions.into_iter().collect()
+    }
+}
+
+/// The highest-weighted `MAX_EXPANSIONS` distinct terms, other than the exact one
+fn dedup_by_weight(candidates: Vec<(u64, f32)>, exact_hash: u64) -> Vec<(u64, f32)> {
+    let mut best = FxHashMap::<u64, f32>::default();
+    for (term_hash, weight) in candidates
+        .into_iter()
+        .filter(|(term_hash, _)| *term_hash != exact_hash)
+    {
+        let entry = best.entry(term_hash).or_default();
+        *entry = entry.max(weight);
+    }
+    let mut best = best.into_iter().collect::<Vec<_>>();
+    best.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    best.truncate(MAX_EXPANSIONS);
+    best
 }
 
 impl SearchIndex {
+    /// Load the cached index for a crate, or build and cache it if it is missing, older than the
+    /// crate's docs, or was scored with different weights
     pub(crate) fn load_or_build<'a>(
         request: &'a Request,
         crate_name: &str,
+        weights: FieldWeights,
     ) -> Result<Self, Vec<Suggestion<'a>>> {
         let mut suggestions = vec![];
 
         let item = request
             .resolve_path(crate_name, &mut suggestions)
             .ok_or(suggestions)?;
+        Ok(Self::for_root(item, weights))
+    }
+
+    /// Load or build the index for a crate whose docs are already built, without building them
+    pub(crate) fn load_built(
+        request: &Request,
+        crate_name: &str,
+        weights: FieldWeights,
+    ) -> Option<Self> {
+        let item = request.built_crate_root(crate_name)?;
+        Some(Self::for_root(item, weights))
+    }
 
+    /// The index of the crate whose root module is `item`, cached next to its docs
+    fn for_root(item: DocRef<'_, Item>, weights: FieldWeights) -> Self {
         let crate_docs = item.crate_docs();
         let crate_name = crate_docs.name().to_string();
 
@@ -217,38 +641,53 @@ impl SearchIndex {
             .unwrap()
             .join(format!("{}.index", crate_name.replace('-', "_")));
 
-        if let Some(terms) = Self::load(&path, mtime) {
-            Ok(Self { crate_name, terms })
-        } else {
+        if let Some(terms) = Self::load(&path, mtime, weights) {
+            return Self { crate_name, terms };
+        }
+
+        let _lock = FileLock::acquire_or_warn(&path.with_extension("index.lock"));
+        // another process may have built the index while we waited for the lock
+        let terms = Self::load(&path, mtime, weights).unwrap_or_else(|| {
             let mut terms = Terms::default();
-            terms.recurse(item, &[], false);
-            let terms = terms.finalize();
+            terms.recurse(item, &[], &[], false);
+            let terms = terms.finalize(weights);
             Self::store(&terms, &path);
-            Ok(Self { terms, crate_name })
-        }
+            terms
+        });
+        Self { terms, crate_name }
     }
 
     fn store(terms: &SearchableTerms, path: &Path) {
-        if let Ok(mut file) = OpenOptions::new().create_new(true).write(true).open(path)
-            && bincode::encode_into_std_write(terms, &mut file, config::standard()).is_err()
-        {
-            let _ = std::fs::remove_file(path);
+        let result = write_atomically(path, |writer| {
+            bincode::encode_into_std_write(IndexHeader::current(), writer, config::standard())
+                .and_then(|_| bincode::encode_into_std_write(terms, writer, config::standard()))
+                .map(|_| ())
+                .map_err(io::Error::other)
+        });
+        if let Err(error) = result {
+            log::warn!("could not write search index {}: {error}", path.display());
         }
     }
 
-    fn load(path: &Path, mtime: Option<SystemTime>) -> Option<SearchableTerms> {
-        let mut file = File::open(path).ok()?;
-        let index_mtime = file.metadata().ok().and_then(|m| m.modified().ok())?;
-
-        let mtime = mtime?;
-        if index_mtime.duration_since(mtime).is_ok()
-            && let Ok(terms) = bincode::decode_from_std_read(&mut file, config::standard())
-        {
-            Some(terms)
-        } else {
-            let _ = std::fs::remove_file(path);
-            None
+    fn load(
+        path: &Path,
+        mtime: Option<SystemTime>,
+        weights: FieldWeights,
+    ) -> Option<SearchableTerms> {
+        let mut file = BufReader::new(File::open(path).ok()?);
+        let index_mtime = file.get_ref().metadata().ok()?.modified().ok()?;
+        index_mtime.duration_since(mtime?).ok()?;
+
+        let header: IndexHeader =
+            bincode::decode_from_std_read(&mut file, config::standard()).ok()?;
+        if header != IndexHeader::current() {
+            log::debug!("rebuilding {} written by {header:?}", path.display());
+            return None;
         }
+
+        let terms: SearchableTerms =
+            bincode::decode_from_std_read(&mut file, config::standard()).ok()?;
+        (terms.weights == weights).then_some(terms)
     }
 
     // /// Build a search index from rustdoc data
@@ -264,122 +703,201 @@ impl SearchIndex {
     //         .ok_or(suggestions)?;
 
     //     let crate_name = item.crate_docs().name().to_string();
-    //     terms.recurse(item, &[], false);
+    //     terms.recurse(item, &[], &[], false);
 
-    //     let terms = terms.finalize();
+    //     let terms = terms.finalize(FieldWeights::default());
 
     //     Ok(Self { terms, crate_name })
     // }
 
     /// Search for items containing the given term
+    ///
+    /// Scores are divided by the largest IDF a term in this crate can have, so that they can be
+    /// compared with scores from crates of a different size.
     pub(crate) fn search(&self, term: &str) -> Vec<(&[u32], f32)> {
-        self.terms.search(term)
+        let scale = (self.terms.ids.len() as f32).ln().max(1.0);
+        let mut results = self.terms.search(term);
+        for (_, score) in &mut results {
+            *score /= scale;
+        }
+        results
     }
 }
 
-fn add_token<'a>(token: &'a str, tokens: &mut Vec<&'a str>) {
-    if let Some(token) = token.strip_suffix('s') {
-        tokens.push(token);
-    } else {
-        tokens.push(token);
-    }
+/// Hash a term for use as a map key
+fn hash_term(term: &str) -> u64 {
+    let mut hasher = FxHasher::default();
+    term.to_lowercase().hash(&mut hasher);
+    hasher.finish()
 }
 
-/// Simple tokenizer: split on whitespace and punctuation, lowercase, filter short words
-fn tokenize(text: &str) -> Vec<&str> {
-    let mut tokens = vec![];
-    let min_chars = 2;
-    let mut last_case = None;
-    let mut word_start = 0;
-    let mut subword_start = 0;
-    let mut word_start_next_char = true;
-    let mut subword_start_next_char = true;
-
-    for (i, c) in text.char_indices() {
-        if word_start_next_char {
-            word_start = i;
-            subword_start = i;
-            word_start_next_char = false;
-            subword_start_next_char = false;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if subword_start_next_char {
-            subword_start = i;
-            subword_start_next_char = false;
+    #[test]
+    fn prefix_and_fuzzy_matches_rank_below_exact() {
+        let mut terms = Terms::default();
+        let documents = [
+            "Serialize a value",
+            "Deserializer for values",
+            "HashMap of entries",
+            "serializer configuration",
+        ];
+        for (index, text) in documents.iter().enumerate() {
+            let id = (0, index as u32);
+            terms.add_terms(text, id, Field::Body);
+            terms.shortest_paths.insert(id, vec![index as u32]);
         }
+        let terms = terms.finalize(FieldWeights::default());
+        let ids = |results: Vec<(&[u32], f32)>| {
+            results
+                .into_iter()
+                .map(|(path, _)| path[0])
+                .collect::<Vec<_>>()
+        };
 
-        let current_case = c.is_alphabetic().then(|| c.is_uppercase());
-        let case_change = last_case == Some(false) && current_case == Some(true);
-        last_case = current_case;
+        assert_eq!(ids(terms.search("Serializ")), vec![0, 3]);
+        assert_eq!(ids(terms.search("deserialise")), vec![1]);
+        assert_eq!(ids(terms.search("HashMp")), vec![2]);
+        // "serialize" and "serializer" share a stem, so both are exact hits
+        assert_eq!(ids(terms.search("serialize")), vec![0, 3]);
+        assert!(terms.search("unrelated").is_empty());
+    }
 
-        if c == '-' || c == '_' {
-            if i.saturating_sub(subword_start) > min_chars {
-                add_token(&text[subword_start..i], &mut tokens);
-            }
-            subword_start_next_char = true;
-        } else if !c.is_alphabetic() {
-            if i.saturating_sub(subword_start) > min_chars && subword_start != word_start {
-                add_token(&text[subword_start..i], &mut tokens);
-            }
-            if i.saturating_sub(word_start) > min_chars {
-                add_token(&text[word_start..i], &mut tokens);
-            }
-            word_start_next_char = true;
-        } else if case_change {
-            if i.saturating_sub(subword_start) > min_chars {
-                add_token(&text[subword_start..i], &mut tokens);
-            }
-            subword_start = i;
+    #[test]
+    fn synonyms_and_short_identifiers_match() {
+        let mut terms = Terms::default();
+        let documents = [
+            ("Vec", "A contiguous growable array type"),
+            ("io", "Traits and helpers for I/O"),
+            ("Rc", "Single-threaded reference-counting pointers"),
+        ];
+        for (index, (name, docs)) in documents.iter().enumerate() {
+            let id = (0, index as u32);
+            terms.add_terms(name, id, Field::Name);
+            terms.add_terms(docs, id, Field::Summary);
+            terms.shortest_paths.insert(id, vec![index as u32]);
         }
-    }
+        let terms = terms.finalize(FieldWeights::default());
+        let top_result = |query: &str| terms.search(query).first().map(|(path, _)| path[0]);
 
-    if !word_start_next_char {
-        let last_subword = &text[subword_start..];
+        assert_eq!(top_result("vector"), Some(0));
+        assert_eq!(top_result("io"), Some(1));
+        assert_eq!(top_result("rc"), Some(2));
+        assert_eq!(top_result("pointer"), Some(2));
+    }
 
-        if word_start != subword_start && last_subword.len() > min_chars {
-            add_token(last_subword, &mut tokens);
+    #[test]
+    fn stopwords_in_names_are_searchable() {
+        let mut terms = Terms::default();
+        let documents = [
+            ("From", "Used to do value-to-value conversions"),
+            ("parse", "Read a value from a string"),
+            ("into_iter", "Creates an iterator from a value"),
+        ];
+        for (index, (name, docs)) in documents.iter().enumerate() {
+            let id = (0, index as u32);
+            terms.add_terms(name, id, Field::Name);
+            terms.add_terms(docs, id, Field::Summary);
+            terms.shortest_paths.insert(id, vec![index as u32]);
         }
+        let terms = terms.finalize(FieldWeights::default());
+        let ids = |query: &str| {
+            terms
+                .search(query)
+                .into_iter()
+                .map(|(path, _)| path[0])
+                .collect::<Vec<_>>()
+        };
 
-        let last_word = &text[word_start..];
-        if last_word.len() > min_chars {
-            add_token(last_word, &mut tokens);
-        }
+        // "from" in docs is a stopword, but `From` is a name
+        assert_eq!(ids("from"), vec![0]);
+        assert_eq!(ids("into iterator"), vec![2]);
+        // stopwords no name contains are still ignored
+        assert_eq!(ids("the value"), ids("value"));
     }
 
-    tokens
-}
+    #[test]
+    fn long_docs_do_not_dominate() {
+        let long_docs =
+            "The parser reads input and reports errors with line and column. ".repeat(3);
+        let top_result = |weights: FieldWeights| {
+            let mut terms = Terms::default();
+            for (index, (name, docs)) in [("Parser", "Reads input"), ("Reader", &*long_docs)]
+                .into_iter()
+                .enumerate()
+            {
+                let id = (0, index as u32);
+                terms.add_terms(name, id, Field::Name);
+                terms.add_terms(docs, id, Field::Summary);
+                terms.shortest_paths.insert(id, vec![index as u32]);
+            }
+            terms.finalize(weights).search("parser")[0].0[0]
+        };
 
-/// Hash a term for use as a map key
-fn hash_term(term: &str) -> u64 {
-    let mut hasher = FxHasher::default();
-    term.to_lowercase().hash(&mut hasher);
-    hasher.finish()
-}
+        assert_eq!(top_result(FieldWeights::default()), 0);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // without normalizing for document length, the repetition outweighs even a name match
+        let weights = FieldWeights {
+            b: 0.0,
+            ..FieldWeights::default()
+        };
+        assert_eq!(top_result(weights), 1);
+    }
 
     #[test]
-    fn test_tokenize() {
+    fn weights_can_be_overridden() {
+        let weights = FieldWeights::default()
+            .with_overrides("name=6, b=0.5,")
+            .unwrap();
         assert_eq!(
-            tokenize("Hello, worlds! This is a test. CamelCases hyphenate-words snake_words"),
-            vec![
-                "Hello",
-                "world",
-                "Thi",
-                "test",
-                "Camel",
-                "Case",
-                "CamelCase",
-                "hyphenate",
-                "word",
-                "hyphenate-word",
-                "snake",
-                "word",
-                "snake_word"
-            ]
+            weights,
+            FieldWeights {
+                name: 6.0,
+                b: 0.5,
+                ..FieldWeights::default()
+            }
         );
+
+        for invalid in ["name", "name=big", "name=-1", "title=2"] {
+            assert!(
+                FieldWeights::default().with_overrides(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn index_files_from_other_versions_are_rebuilt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crate.index");
+        let mut terms = Terms::default();
+        terms.add_terms("Parser", (0, 0), Field::Name);
+        terms.shortest_paths.insert((0, 0), vec![0]);
+        let weights = FieldWeights::default();
+        let terms = terms.finalize(weights);
+        SearchIndex::store(&terms, &path);
+
+        let built = Some(SystemTime::UNIX_EPOCH);
+        assert!(SearchIndex::load(&path, built, weights).is_some());
+
+        let mut older = bincode::encode_to_vec(
+            IndexHeader {
+                format_version: 0,
+                ..IndexHeader::current()
+            },
+            config::standard(),
+        )
+        .unwrap();
+        older.extend(&std::fs::read(&path).unwrap()[older.len()..]);
+        std::fs::write(&path, older).unwrap();
+        assert!(SearchIndex::load(&path, built, weights).is_none());
+
+        // 0.1.4 and earlier wrote the terms without any header
+        let headerless = bincode::encode_to_vec(&terms, config::standard()).unwrap();
+        std::fs::write(&path, headerless).unwrap();
+        assert!(SearchIndex::load(&path, built, weights).is_none());
     }
 
     #[test]
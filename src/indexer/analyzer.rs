@@ -0,0 +1,212 @@
+//! Text analysis shared by indexing and querying: splitting text into words, dropping stopwords,
+//! and reducing each word to a term by synonym folding and stemming
+
+use super::stemmer::stem;
+
+/// Common English words that carry no meaning in a search
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "been", "but", "by", "can", "do", "does", "for",
+    "from", "has", "have", "if", "in", "into", "is", "it", "its", "may", "of", "on", "or", "such",
+    "than", "that", "the", "their", "then", "there", "these", "this", "those", "to", "was", "were",
+    "when", "which", "will", "with",
+];
+
+/// Words that Rust code and its documentation use interchangeably, with the form both are
+/// indexed under
+const SYNONYMS: &[(&str, &str)] = &[
+    ("vector", "vec"),
+    ("str", "string"),
+    ("err", "error"),
+    ("mutable", "mut"),
+    ("function", "fn"),
+    ("pointer", "ptr"),
+    ("length", "len"),
+];
+
+/// A word from analyzed text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Token {
+    /// The word as written, lowercased, for prefix and fuzzy matching
+    pub(super) surface: String,
+    /// What the word is indexed and looked up under
+    pub(super) term: String,
+}
+
+/// Split prose into tokens, for docs
+///
+/// Identifiers are split into their parts at underscores, hyphens and case changes, with each
+/// part followed by the whole identifier. Words of a single character, numbers and stopwords
+/// are dropped; short identifiers like `io` and `Rc` are kept.
+pub(super) fn analyze(text: &str) -> Vec<Token> {
+    tokens(text)
+        .filter(|token| !is_stopword(&token.surface))
+        .collect()
+}
+
+/// Split names, paths and queries into tokens like [`analyze`], but keeping stopwords, which
+/// are meaningful in names like `From`, `into_iter` and `as_ref`
+pub(super) fn analyze_names(text: &str) -> Vec<Token> {
+    tokens(text).collect()
+}
+
+/// Whether a lowercased word is too common in prose to be worth indexing
+pub(super) fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(&word)
+}
+
+fn tokens(text: &str) -> impl Iterator<Item = Token> + '_ {
+    split_words(text).into_iter().filter_map(|word| {
+        let surface = word.to_lowercase();
+        if surface.chars().count() < 2 || surface.chars().all(|c| c.is_numeric()) {
+            return None;
+        }
+        let term = canonical_term(&surface);
+        Some(Token { surface, term })
+    })
+}
+
+/// The term a lowercased word is indexed under
+fn canonical_term(word: &str) -> String {
+    let synonym = |word: &str| {
+        SYNONYMS
+            .iter()
+            .find(|(synonym, _)| *synonym == word)
+            .map(|(_, canonical)| canonical.to_string())
+    };
+    synonym(word).unwrap_or_else(|| {
+        let stemmed = stem(word);
+        synonym(&stemmed).unwrap_or(stemmed)
+    })
+}
+
+/// Split text into words and the parts of compound identifiers, e.g. `HashMap` into `Hash`,
+/// `Map` and `HashMap`
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    for word in text.split(|c: char| !is_word_char(c)) {
+        let word = word.trim_matches(['_', '-']);
+        if word.is_empty() {
+            continue;
+        }
+
+        let parts = split_identifier(word);
+        let compound = parts.len() > 1;
+        words.extend(parts);
+        if compound {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Split an identifier at underscores, hyphens and case changes, keeping acronyms together,
+/// e.g. `parse_HTTPRequest` into `parse`, `HTTP` and `Request`
+fn split_identifier(word: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let chars = word.char_indices().collect::<Vec<_>>();
+    let mut start = 0;
+    for (index, &(i, c)) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            if start < i {
+                parts.push(&word[start..i]);
+            }
+            start = i + c.len_utf8();
+            continue;
+        }
+
+        let previous = index.checked_sub(1).map(|index| chars[index].1);
+        let next = chars.get(index + 1).map(|(_, c)| *c);
+        let boundary = c.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_numeric()
+                    || (previous.is_uppercase() && next.is_some_and(char::is_lowercase))
+            });
+        if boundary && start < i {
+            parts.push(&word[start..i]);
+            start = i;
+        }
+    }
+    if start < word.len() {
+        parts.push(&word[start..]);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(text: &str) -> Vec<String> {
+        analyze(text).into_iter().map(|token| token.term).collect()
+    }
+
+    #[test]
+    fn splits_identifiers() {
+        assert_eq!(
+            split_words("CamelCase hyphenate-words snake_words parse_HTTPRequest u8 io::Read"),
+            vec![
+                "Camel",
+                "Case",
+                "CamelCase",
+                "hyphenate",
+                "words",
+                "hyphenate-words",
+                "snake",
+                "words",
+                "snake_words",
+                "parse",
+                "HTTP",
+                "Request",
+                "parse_HTTPRequest",
+                "u8",
+                "io",
+                "Read",
+            ]
+        );
+    }
+
+    #[test]
+    fn stems_and_drops_stopwords() {
+        assert_eq!(
+            terms("Hello, worlds! This is a test of the parsing, 42 times."),
+            vec!["hello", "world", "test", "pars", "time"]
+        );
+    }
+
+    #[test]
+    fn names_keep_stopwords() {
+        let terms = |text| {
+            analyze_names(text)
+                .into_iter()
+                .map(|token| token.surface)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(terms("From"), vec!["from"]);
+        assert_eq!(terms("into_iter"), vec!["into", "iter", "into_iter"]);
+    }
+
+    #[test]
+    fn keeps_short_identifiers() {
+        assert_eq!(
+            terms("std::io::fs Rc Arc mpsc"),
+            vec!["std", "io", "fs", "rc", "arc", "mpsc"]
+        );
+    }
+
+    #[test]
+    fn folds_synonyms() {
+        assert_eq!(terms("vector vectors Vec"), vec!["vec", "vec", "vec"]);
+        assert_eq!(terms("str String strings"), vec!["string"; 3]);
+        assert_eq!(terms("err Error errors"), vec!["error"; 3]);
+        assert_eq!(terms("mutable mut"), vec!["mut"; 2]);
+        assert_eq!(
+            analyze("as_mut_str")
+                .into_iter()
+                .map(|token| token.surface)
+                .collect::<Vec<_>>(),
+            vec!["mut", "str", "as_mut_str"]
+        );
+    }
+}
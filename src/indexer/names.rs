@@ -0,0 +1,154 @@
+//! An index of the names in a crate's public modules and the paths they are reachable at, for
+//! suggesting where an item that was not found at the path given is
+
+use super::IndexHeader;
+use crate::doc_ref::DocRef;
+use crate::fs_utils::write_atomically;
+use crate::string_utils::case_aware_jaro_winkler;
+use bincode::{Decode, Encode, config};
+use rustc_hash::FxHashSet;
+use rustdoc_types::{Id, Item, ItemEnum};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// How many modules deep to index names
+const MAX_MODULE_DEPTH: usize = 8;
+
+/// The names in one crate's public modules
+#[derive(Debug, Default, Encode, Decode)]
+pub(crate) struct NameIndex {
+    /// Every path each name is reachable at, relative to the crate root
+    names: BTreeMap<String, Vec<String>>,
+}
+
+impl NameIndex {
+    /// Load the cached name index for the crate documented at `json_path`, or build and cache
+    /// it if it is missing or older than the docs
+    ///
+    /// `root` gives the crate's root module, and is only called to build the index. Returns
+    /// None if the docs are not built.
+    pub(crate) fn load_or_build<'a>(
+        json_path: &Path,
+        root: impl FnOnce() -> Option<DocRef<'a, Item>>,
+    ) -> Option<Self> {
+        let mtime = json_path.metadata().ok()?.modified().ok()?;
+        let path = json_path.with_extension("names");
+        if let Some(index) = Self::load(&path, mtime) {
+            return Some(index);
+        }
+
+        let root = root()?;
+        let mut index = Self::default();
+        let mut visited = FxHashSet::default();
+        visited.insert((root.crate_docs().name(), root.id));
+        index.add_module(root, None, 0, &mut visited);
+        index.store(&path);
+        Some(index)
+    }
+
+    /// Add the names in a module and the public modules below it
+    fn add_module<'a>(
+        &mut self,
+        module: DocRef<'a, Item>,
+        prefix: Option<&str>,
+        depth: usize,
+        visited: &mut FxHashSet<(&'a str, Id)>,
+    ) {
+        for child in module.child_items() {
+            let Some(child_name) = child.name() else {
+                continue;
+            };
+            let child_path = match prefix {
+                Some(prefix) => format!("{prefix}::{child_name}"),
+                None => child_name.to_string(),
+            };
+            self.names
+                .entry(child_name.to_string())
+                .or_default()
+                .push(child_path.clone());
+
+            if matches!(child.inner(), ItemEnum::Module(_))
+                && depth < MAX_MODULE_DEPTH
+                && visited.insert((child.crate_docs().name(), child.id))
+            {
+                self.add_module(child, Some(&child_path), depth + 1, visited);
+            }
+        }
+    }
+
+    /// The paths of names that match `name` at least as closely as `min_score`, with how
+    /// closely they match
+    pub(crate) fn similar<'a>(
+        &'a self,
+        name: &'a str,
+        min_score: f64,
+    ) -> impl Iterator<Item = (&'a str, f64)> {
+        self.names
+            .iter()
+            .filter_map(move |(candidate, paths)| {
+                let score = case_aware_jaro_winkler(name, candidate);
+                (score >= min_score).then(|| paths.iter().map(move |path| (path.as_str(), score)))
+            })
+            .flatten()
+    }
+
+    fn store(&self, path: &Path) {
+        let result = write_atomically(path, |writer| {
+            bincode::encode_into_std_write(IndexHeader::current(), writer, config::standard())
+                .and_then(|_| bincode::encode_into_std_write(self, writer, config::standard()))
+                .map(|_| ())
+                .map_err(io::Error::other)
+        });
+        if let Err(error) = result {
+            log::warn!("could not write name index {}: {error}", path.display());
+        }
+    }
+
+    fn load(path: &Path, mtime: SystemTime) -> Option<Self> {
+        let mut file = BufReader::new(File::open(path).ok()?);
+        let index_mtime = file.get_ref().metadata().ok()?.modified().ok()?;
+        index_mtime.duration_since(mtime).ok()?;
+
+        let header: IndexHeader =
+            bincode::decode_from_std_read(&mut file, config::standard()).ok()?;
+        if header != IndexHeader::current() {
+            return None;
+        }
+        bincode::decode_from_std_read(&mut file, config::standard()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_stored_and_matched_fuzzily() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crate.names");
+        let index = NameIndex {
+            names: BTreeMap::from([
+                (
+                    "Mutex".to_string(),
+                    vec!["sync::Mutex".to_string(), "Mutex".to_string()],
+                ),
+                ("Vec".to_string(), vec!["vec::Vec".to_string()]),
+            ]),
+        };
+        index.store(&path);
+
+        let loaded = NameIndex::load(&path, SystemTime::UNIX_EPOCH).unwrap();
+        let similar = loaded
+            .similar("Mutx", 0.9)
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        assert_eq!(similar, ["sync::Mutex", "Mutex"]);
+
+        // an index older than the docs is rebuilt
+        let newer = SystemTime::now() + std::time::Duration::from_secs(60);
+        assert!(NameIndex::load(&path, newer).is_none());
+    }
+}
@@ -0,0 +1,310 @@
+//! The Porter stemming algorithm, as described in M.F. Porter, "An algorithm for suffix
+//! stripping", 1980, and following the reference C implementation
+
+/// Reduce a lowercase English word to its stem, e.g. "connections" and "connected" to "connect"
+///
+/// Words that are not entirely ASCII letters, or are shorter than three letters, are returned
+/// unchanged.
+pub(super) fn stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+
+    let mut stemmer = Stemmer {
+        b: word.as_bytes().to_vec(),
+        k: word.len() as isize - 1,
+        j: 0,
+    };
+    stemmer.step1ab();
+    if stemmer.k > 0 {
+        stemmer.step1c();
+        stemmer.step2();
+        stemmer.step3();
+        stemmer.step4();
+        stemmer.step5();
+    }
+
+    stemmer.b.truncate(stemmer.k as usize + 1);
+    String::from_utf8(stemmer.b).unwrap_or_else(|_| word.to_string())
+}
+
+struct Stemmer {
+    b: Vec<u8>,
+    /// Index of the last byte of the current word
+    k: isize,
+    /// Index of the last byte before a matched suffix
+    j: isize,
+}
+
+impl Stemmer {
+    fn at(&self, i: isize) -> u8 {
+        self.b[i as usize]
+    }
+
+    fn is_consonant(&self, i: isize) -> bool {
+        match self.at(i) {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.is_consonant(i - 1),
+            _ => true,
+        }
+    }
+
+    /// The number of vowel-consonant sequences in `b[..=j]`
+    fn measure(&self) -> usize {
+        let mut n = 0;
+        let mut i = 0;
+        loop {
+            if i > self.j {
+                return n;
+            }
+            if !self.is_consonant(i) {
+                break;
+            }
+            i += 1;
+        }
+        i += 1;
+        loop {
+            loop {
+                if i > self.j {
+                    return n;
+                }
+                if self.is_consonant(i) {
+                    break;
+                }
+                i += 1;
+            }
+            i += 1;
+            n += 1;
+            loop {
+                if i > self.j {
+                    return n;
+                }
+                if !self.is_consonant(i) {
+                    break;
+                }
+                i += 1;
+            }
+            i += 1;
+        }
+    }
+
+    fn vowel_in_stem(&self) -> bool {
+        (0..=self.j).any(|i| !self.is_consonant(i))
+    }
+
+    fn double_consonant(&self, i: isize) -> bool {
+        i >= 1 && self.at(i) == self.at(i - 1) && self.is_consonant(i)
+    }
+
+    /// Whether `b[i-2..=i]` is consonant-vowel-consonant, with the last not w, x or y
+    fn cvc(&self, i: isize) -> bool {
+        i >= 2
+            && self.is_consonant(i)
+            && !self.is_consonant(i - 1)
+            && self.is_consonant(i - 2)
+            && !matches!(self.at(i), b'w' | b'x' | b'y')
+    }
+
+    /// Whether the word ends with `suffix`, setting `j` to just before it if so
+    fn ends(&mut self, suffix: &str) -> bool {
+        let length = suffix.len() as isize;
+        if length > self.k + 1 {
+            return false;
+        }
+        let start = (self.k - length + 1) as usize;
+        if &self.b[start..=self.k as usize] != suffix.as_bytes() {
+            return false;
+        }
+        self.j = self.k - length;
+        true
+    }
+
+    /// Replace everything after `j` with `replacement`
+    fn set_to(&mut self, replacement: &str) {
+        self.b.truncate((self.j + 1) as usize);
+        self.b.extend_from_slice(replacement.as_bytes());
+        self.k = self.j + replacement.len() as isize;
+    }
+
+    fn replace_if_measured(&mut self, replacement: &str) {
+        if self.measure() > 0 {
+            self.set_to(replacement);
+        }
+    }
+
+    /// Plurals and -ed or -ing
+    fn step1ab(&mut self) {
+        if self.at(self.k) == b's' {
+            if self.ends("sses") {
+                self.k -= 2;
+            } else if self.ends("ies") {
+                self.set_to("i");
+            } else if self.at(self.k - 1) != b's' {
+                self.k -= 1;
+            }
+        }
+
+        if self.ends("eed") {
+            if self.measure() > 0 {
+                self.k -= 1;
+            }
+        } else if (self.ends("ed") || self.ends("ing")) && self.vowel_in_stem() {
+            self.k = self.j;
+            if self.ends("at") {
+                self.set_to("ate");
+            } else if self.ends("bl") {
+                self.set_to("ble");
+            } else if self.ends("iz") {
+                self.set_to("ize");
+            } else if self.double_consonant(self.k) {
+                self.k -= 1;
+                if matches!(self.at(self.k), b'l' | b's' | b'z') {
+                    self.k += 1;
+                }
+            } else {
+                self.j = self.k;
+                if self.measure() == 1 && self.cvc(self.k) {
+                    self.set_to("e");
+                }
+            }
+        }
+    }
+
+    /// Terminal y to i when there is another vowel in the stem
+    fn step1c(&mut self) {
+        if self.ends("y") && self.vowel_in_stem() {
+            let k = self.k as usize;
+            self.b[k] = b'i';
+        }
+    }
+
+    /// Double suffixes to single ones, e.g. -ization to -ize
+    fn step2(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("ational", "ate"),
+            ("tional", "tion"),
+            ("enci", "ence"),
+            ("anci", "ance"),
+            ("izer", "ize"),
+            ("bli", "ble"),
+            ("alli", "al"),
+            ("entli", "ent"),
+            ("eli", "e"),
+            ("ousli", "ous"),
+            ("ization", "ize"),
+            ("ation", "ate"),
+            ("ator", "ate"),
+            ("alism", "al"),
+            ("iveness", "ive"),
+            ("fulness", "ful"),
+            ("ousness", "ous"),
+            ("aliti", "al"),
+            ("iviti", "ive"),
+            ("biliti", "ble"),
+            ("logi", "log"),
+        ];
+        self.apply_first(RULES);
+    }
+
+    /// -ic-, -full, -ness etc.
+    fn step3(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("icate", "ic"),
+            ("ative", ""),
+            ("alize", "al"),
+            ("iciti", "ic"),
+            ("ical", "ic"),
+            ("ful", ""),
+            ("ness", ""),
+        ];
+        self.apply_first(RULES);
+    }
+
+    /// Replace the first matching suffix, if what precedes it has a non-zero measure
+    ///
+    /// Rules are checked in order, and once one matches no later rule is tried, as in the
+    /// reference implementation's switch statements.
+    fn apply_first(&mut self, rules: &[(&str, &str)]) {
+        for (suffix, replacement) in rules {
+            if self.ends(suffix) {
+                self.replace_if_measured(replacement);
+                return;
+            }
+        }
+    }
+
+    /// -ant, -ence etc. in a word with a measure above one
+    fn step4(&mut self) {
+        const SUFFIXES: &[&str] = &[
+            "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion",
+            "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+        ];
+        let matched = SUFFIXES.iter().any(|suffix| {
+            self.ends(suffix)
+                && (*suffix != "ion" || (self.j >= 0 && matches!(self.at(self.j), b's' | b't')))
+        });
+        if matched && self.measure() > 1 {
+            self.k = self.j;
+        }
+    }
+
+    /// Remove a final -e, and -ll to -l, in longer words
+    fn step5(&mut self) {
+        self.j = self.k;
+        if self.at(self.k) == b'e' {
+            let measure = self.measure();
+            if measure > 1 || (measure == 1 && !self.cvc(self.k - 1)) {
+                self.k -= 1;
+            }
+        }
+        if self.at(self.k) == b'l' && self.double_consonant(self.k) && self.measure() > 1 {
+            self.k -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porter_examples() {
+        for (word, expected) in [
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("cats", "cat"),
+            ("feed", "feed"),
+            ("agreed", "agre"),
+            ("plastered", "plaster"),
+            ("motoring", "motor"),
+            ("sing", "sing"),
+            ("conflated", "conflat"),
+            ("troubled", "troubl"),
+            ("sized", "size"),
+            ("hopping", "hop"),
+            ("falling", "fall"),
+            ("hissing", "hiss"),
+            ("filing", "file"),
+            ("happy", "happi"),
+            ("relational", "relat"),
+            ("conditional", "condit"),
+            ("rational", "ration"),
+            ("digitizer", "digit"),
+            ("triplicate", "triplic"),
+            ("hopeful", "hope"),
+            ("goodness", "good"),
+            ("revival", "reviv"),
+            ("adjustment", "adjust"),
+            ("adoption", "adopt"),
+            ("controll", "control"),
+            ("rate", "rate"),
+            ("serialize", "serial"),
+            ("serializer", "serial"),
+            ("iterators", "iter"),
+            ("io", "io"),
+            ("u8", "u8"),
+        ] {
+            assert_eq!(stem(word), expected, "stemming {word}");
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::doc_ref::DocRef;
 use fieldwork::Fieldwork;
-use rustdoc_types::{Id, Item, ItemEnum, Type, Use};
+use rustdoc_types::{Id, Item, ItemEnum, StructKind, Type, Use, VariantKind};
 use std::collections::hash_map::Values;
 
 pub(crate) struct MethodIter<'a> {
@@ -32,6 +32,110 @@ impl<'a> DocRef<'a, Item> {
     pub(crate) fn child_items(&self) -> ChildItems<'a> {
         ChildItems::new(*self)
     }
+
+    /// Fields, trait items, and the items of this item's own impls that are not among its
+    /// [`child_items`](Self::child_items), including the methods its traits provide
+    ///
+    /// A path can name these below the item, but they are not listed as its children.
+    pub(crate) fn members(&self) -> impl Iterator<Item = DocRef<'a, Item>> + use<'a> {
+        self.own_members().chain(self.trait_impl_members())
+    }
+
+    /// The [`members`](Self::members) that do not come from a trait impl: fields, trait items,
+    /// and the items of inherent impls that are not among the [`child_items`](Self::child_items)
+    pub(crate) fn own_members(&self) -> impl Iterator<Item = DocRef<'a, Item>> + use<'a> {
+        let item = *self;
+        let no_ids: &[Id] = &[];
+        let (fields, trait_items) = match item.inner() {
+            ItemEnum::Struct(struct_data) => match &struct_data.kind {
+                StructKind::Unit => (vec![], no_ids),
+                StructKind::Tuple(fields) => (fields.iter().flatten().copied().collect(), no_ids),
+                StructKind::Plain { fields, .. } => (fields.clone(), no_ids),
+            },
+            ItemEnum::Union(union_data) => (union_data.fields.clone(), no_ids),
+            ItemEnum::Variant(variant) => match &variant.kind {
+                VariantKind::Plain => (vec![], no_ids),
+                VariantKind::Tuple(fields) => (fields.iter().flatten().copied().collect(), no_ids),
+                VariantKind::Struct { fields, .. } => (fields.clone(), no_ids),
+            },
+            ItemEnum::Trait(trait_data) => (vec![], &trait_data.items[..]),
+            _ => (vec![], no_ids),
+        };
+        // inherent methods of structs, enums and primitives are already child items
+        let inherent_impls_are_children = matches!(
+            item.inner(),
+            ItemEnum::Struct(_) | ItemEnum::Enum(_) | ItemEnum::Primitive(_)
+        );
+        let inherent_impls = if inherent_impls_are_children {
+            no_ids
+        } else {
+            item.impl_ids()
+        };
+
+        fields
+            .into_iter()
+            .filter_map(move |id| item.get(&id))
+            .chain(item.id_iter(trait_items))
+            .chain(
+                item.id_iter(inherent_impls)
+                    .filter(|impl_block| {
+                        matches!(impl_block.inner(), ItemEnum::Impl(impl_data) if impl_data.trait_.is_none())
+                    })
+                    .flat_map(|impl_block| impl_block.impl_items()),
+            )
+    }
+
+    /// The items of this item's own trait impls, including the methods the traits provide
+    pub(crate) fn trait_impl_members(&self) -> impl Iterator<Item = DocRef<'a, Item>> + use<'a> {
+        self.id_iter(self.impl_ids())
+            .filter(|impl_block| {
+                let ItemEnum::Impl(impl_data) = impl_block.inner() else {
+                    return false;
+                };
+                // blanket impls like `impl<T> From<T> for T` would add the same methods to
+                // every type
+                impl_data.trait_.is_some()
+                    && impl_data.blanket_impl.is_none()
+                    && !impl_data.is_synthetic
+            })
+            .flat_map(|impl_block| impl_block.impl_items())
+    }
+
+    /// The impls rustdoc lists on a struct, union, enum or primitive
+    fn impl_ids(&self) -> &'a [Id] {
+        match self.inner() {
+            ItemEnum::Struct(struct_data) => &struct_data.impls,
+            ItemEnum::Union(union_data) => &union_data.impls,
+            ItemEnum::Enum(enum_data) => &enum_data.impls,
+            ItemEnum::Primitive(primitive) => &primitive.impls,
+            _ => &[],
+        }
+    }
+
+    /// The items of an impl block, followed by the methods its trait provides that it does not
+    /// override
+    pub(crate) fn impl_items(&self) -> Vec<DocRef<'a, Item>> {
+        let ItemEnum::Impl(impl_data) = self.inner() else {
+            return vec![];
+        };
+        let mut items = self.id_iter(&impl_data.items).collect::<Vec<_>>();
+        if let Some(trait_path) = &impl_data.trait_
+            && !impl_data.provided_trait_methods.is_empty()
+            && let Some(trait_item) = self.get_or_external(&trait_path.id)
+            && let ItemEnum::Trait(trait_data) = trait_item.inner()
+        {
+            items.extend(
+                trait_item
+                    .id_iter(&trait_data.items)
+                    .filter(|trait_member| {
+                        trait_member.name().is_some_and(|name| {
+                            impl_data.provided_trait_methods.iter().any(|p| p == name)
+                        })
+                    }),
+            );
+        }
+        items
+    }
 }
 
 impl<'a, T> DocRef<'a, T> {
@@ -43,11 +147,24 @@ impl<'a, T> DocRef<'a, T> {
 pub(crate) struct TraitIter<'a> {
     item: DocRef<'a, Item>,
     item_iter: Values<'a, Id, Item>,
+    /// Impls rustdoc lists on the item, which include blanket impls whose self type is generic
+    listed_impls: &'a [Id],
 }
 impl<'a> TraitIter<'a> {
     fn new(item: DocRef<'a, Item>) -> Self {
         let item_iter = item.crate_docs().index.values();
-        Self { item, item_iter }
+        let listed_impls = match item.inner() {
+            ItemEnum::Struct(struct_data) => &struct_data.impls[..],
+            ItemEnum::Enum(enum_data) => &enum_data.impls[..],
+            ItemEnum::Union(union_data) => &union_data.impls[..],
+            ItemEnum::Primitive(primitive) => &primitive.impls[..],
+            _ => &[],
+        };
+        Self {
+            item,
+            item_iter,
+            listed_impls,
+        }
     }
 }
 
@@ -57,8 +174,8 @@ impl<'a> Iterator for TraitIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         for item in &mut self.item_iter {
             if let ItemEnum::Impl(impl_block) = &item.inner
-                && let Type::ResolvedPath(path) = &impl_block.for_
-                && path.id == self.item.id
+                && (is_impl_for(self.item.item(), &impl_block.for_)
+                    || self.listed_impls.contains(&item.id))
                 && impl_block.trait_.is_some()
             {
                 return Some(self.item.build_ref(item));
@@ -132,12 +249,7 @@ impl<'a, T> Iterator for IdIter<'a, T> {
                             return Some(item);
                         }
 
-                        let source_item = use_item
-                            .id
-                            .and_then(|id| item.crate_docs().get(item.request(), &id))
-                            .or_else(|| {
-                                item.request().resolve_path(&use_item.source, &mut vec![])
-                            })?;
+                        let source_item = item.build_ref(use_item).source_item()?;
 
                         if use_item.is_glob {
                             self.glob_iter = match source_item.inner() {
@@ -185,8 +297,7 @@ impl<'a> Iterator for InherentImplBlockIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         for item in &mut self.item_iter {
             if let ItemEnum::Impl(impl_block) = &item.inner
-                && let Type::ResolvedPath(path) = &impl_block.for_
-                && path.id == self.item.id
+                && is_impl_for(self.item.item(), &impl_block.for_)
                 && impl_block.trait_.is_none()
             {
                 return Some(DocRef::new(self.item.request(), self.item, item));
@@ -196,6 +307,32 @@ impl<'a> Iterator for InherentImplBlockIter<'a> {
     }
 }
 
+/// Whether an impl block whose self type is `for_` implements `item`
+///
+/// Primitives are implemented on by type rather than by path, e.g. `impl<T> [T]` for `slice`.
+fn is_impl_for(item: &Item, for_: &Type) -> bool {
+    match (&item.inner, for_) {
+        (ItemEnum::Primitive(primitive), for_) => is_primitive_type(&primitive.name, for_),
+        (_, Type::ResolvedPath(path)) => path.id == item.id,
+        _ => false,
+    }
+}
+
+/// Whether `type_` is the primitive that rustdoc documents as `name`
+fn is_primitive_type(name: &str, type_: &Type) -> bool {
+    match type_ {
+        Type::Primitive(primitive) => primitive == name,
+        Type::Slice(_) => name == "slice",
+        Type::Array { .. } => name == "array",
+        Type::Tuple(types) if types.is_empty() => name == "unit",
+        Type::Tuple(_) => name == "tuple",
+        Type::BorrowedRef { .. } => name == "reference",
+        Type::RawPointer { .. } => name == "pointer",
+        Type::FunctionPointer(_) => name == "fn",
+        _ => false,
+    }
+}
+
 pub(crate) enum ChildItems<'a> {
     AssociatedMethods(MethodIter<'a>),
     Module(IdIter<'a, Item>),
@@ -221,15 +358,7 @@ impl<'a> Iterator for ChildItems<'a> {
 
                     let name = use_item.name();
 
-                    let source_item = use_item
-                        .id
-                        .and_then(|id| use_item.get(&id))
-                        .or_else(|| {
-                            use_item
-                                .request()
-                                .resolve_path(&use_item.source, &mut vec![])
-                        })?
-                        .with_name(name);
+                    let source_item = use_item.source_item()?.with_name(name);
 
                     if use_item.is_glob {
                         match source_item.inner() {
@@ -275,7 +404,7 @@ impl<'a> ChildItems<'a> {
             ItemEnum::Enum(enum_item) => {
                 Self::Enum(item.id_iter(&enum_item.variants), item.methods())
             }
-            ItemEnum::Struct(_) => Self::AssociatedMethods(item.methods()),
+            ItemEnum::Struct(_) | ItemEnum::Primitive(_) => Self::AssociatedMethods(item.methods()),
             ItemEnum::Use(use_item) => ChildItems::Use(Some(item.build_ref(use_item)), None, false),
             _ => Self::None,
         }
@@ -298,3 +427,23 @@ impl<'a> ChildItems<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitive_impls_match_by_type() {
+        let u8_type = Type::Primitive("u8".into());
+        let slice = Type::Slice(Box::new(u8_type.clone()));
+        assert!(is_primitive_type("u8", &u8_type));
+        assert!(!is_primitive_type("u16", &u8_type));
+        assert!(is_primitive_type("slice", &slice));
+        assert!(!is_primitive_type("u8", &slice));
+        assert!(is_primitive_type("unit", &Type::Tuple(vec![])));
+        assert!(is_primitive_type(
+            "tuple",
+            &Type::Tuple(vec![u8_type.clone(), u8_type])
+        ));
+    }
+}
@@ -1,11 +1,16 @@
 mod doc_ref;
 mod filter;
 mod format_context;
+mod fs_utils;
 mod indent;
 mod indexer;
 mod iterators;
+mod output_format;
+mod pagination;
 mod request;
+mod resource_uri;
 mod rustdoc;
+mod server;
 mod state;
 mod string_utils;
 mod tools;
@@ -15,7 +20,9 @@ mod verbosity;
 use std::{env, path::PathBuf};
 
 use anyhow::Result;
+use clap::Parser;
 use mcplease::server_info;
+use server::ServeArgs;
 use state::RustdocTools;
 use tools::Tools;
 
@@ -30,6 +37,14 @@ fn main() -> Result<()> {
 
     let mut state = RustdocTools::new(storage_path)?;
 
+    if env::args().nth(1).as_deref() == Some("serve") {
+        let args = ServeArgs::parse_from(env::args().skip(1));
+        server::init_logging()?;
+        return server::serve(&mut state, args, server_info!(), Some(INSTRUCTIONS));
+    }
+
+    // Anything else runs a single tool from the command line and exits, so there is nothing to
+    // watch: only `serve` watches projects for changes
     mcplease::run::<Tools, _>(&mut state, server_info!(), Some(INSTRUCTIONS))
 }
 
@@ -0,0 +1,22 @@
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Controls whether tools respond with human-readable text or structured JSON
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ValueEnum, Default,
+)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OutputFormat {
+    /// Preformatted text, suited to reading in a chat
+    #[default]
+    Text,
+    /// A stable JSON schema, suited to programs
+    Json,
+}
+
+impl OutputFormat {
+    pub(crate) fn is_json(self) -> bool {
+        matches!(self, Self::Json)
+    }
+}
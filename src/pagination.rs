@@ -0,0 +1,226 @@
+use crate::traits::WriteFmt;
+use anyhow::{Result, anyhow, bail};
+use rustc_hash::FxHasher;
+use serde::Serialize;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+/// Character budget applied to text responses when the caller does not set `max_chars`
+pub(crate) const DEFAULT_MAX_CHARS: usize = 50_000;
+
+/// Tool arguments that only select a page and so are left out of the request fingerprint
+const PAGE_ARGUMENTS: &[&str] = &["cursor", "max_chars", "max_items"];
+
+/// Output budget for a single response, and where in the full output this page starts
+///
+/// A page is addressed by an offset whose unit depends on what is being paged: entries for
+/// module listings and search results, characters for a single item's text. The offset is
+/// handed to callers as an opaque cursor tied to the arguments of the originating request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Page {
+    max_items: Option<usize>,
+    max_chars: Option<usize>,
+    offset: usize,
+    fingerprint: u64,
+}
+
+impl Page {
+    /// Build a page from tool arguments, validating that `cursor` came from the same request
+    pub(crate) fn new(
+        args: &impl Serialize,
+        max_items: Option<usize>,
+        max_chars: Option<usize>,
+        cursor: Option<&str>,
+    ) -> Result<Self> {
+        let fingerprint = fingerprint(args)?;
+        let offset = match cursor {
+            Some(cursor) => decode_cursor(cursor, fingerprint)?,
+            None => 0,
+        };
+
+        Ok(Self {
+            max_items: max_items.map(|max| max.max(1)),
+            max_chars,
+            offset,
+            fingerprint,
+        })
+    }
+
+    /// Drop the character budget, for output formats where truncating text makes no sense
+    pub(crate) fn without_char_limit(self) -> Self {
+        Self {
+            max_chars: None,
+            ..self
+        }
+    }
+
+    /// The cursor that resumes output at `offset`
+    pub(crate) fn cursor_at(&self, offset: usize) -> String {
+        format!("{offset}.{:016x}", self.fingerprint)
+    }
+
+    /// Choose which of a sequence of entries fit on this page, given each entry's rendered length
+    ///
+    /// At least one entry is always selected when any remain, so an oversized entry cannot
+    /// stall pagination.
+    pub(crate) fn select(&self, lengths: impl IntoIterator<Item = usize>) -> Range<usize> {
+        let mut end = self.offset;
+        let mut chars = 0;
+
+        for (index, length) in lengths.into_iter().enumerate().skip(self.offset) {
+            let count = index - self.offset;
+            if count > 0
+                && (self.max_items.is_some_and(|max| count >= max)
+                    || self.max_chars.is_some_and(|max| chars + length > max))
+            {
+                break;
+            }
+            chars += length;
+            end = index + 1;
+        }
+
+        self.offset..end
+    }
+
+    /// Describe the entries left off this page, or `None` if nothing was omitted
+    ///
+    /// `breakdown` optionally says what kinds of entries were omitted, e.g. "Functions: 12".
+    pub(crate) fn omission_note(
+        &self,
+        shown: &Range<usize>,
+        total: usize,
+        noun: &str,
+        breakdown: Option<String>,
+    ) -> Option<String> {
+        if shown.end >= total {
+            return None;
+        }
+
+        let breakdown = breakdown.map(|b| format!(" ({b})")).unwrap_or_default();
+        Some(format!(
+            "\n[Showing {noun} {}-{} of {total}; {} more omitted{breakdown}. \
+             Repeat the call with `cursor: \"{}\"` for the next page.]\n",
+            shown.start + 1,
+            shown.end,
+            total - shown.end,
+            self.cursor_at(shown.end)
+        ))
+    }
+
+    /// Cut text to this page's character budget, breaking at a line boundary where possible
+    pub(crate) fn truncate_text(&self, text: &str) -> String {
+        let start = floor_char_boundary(text, self.offset);
+        let rest = &text[start..];
+
+        let Some(max_chars) = self.max_chars.filter(|max| rest.len() > *max) else {
+            return rest.to_string();
+        };
+
+        let limit = floor_char_boundary(rest, max_chars);
+        let end = match rest[..limit].rfind('\n') {
+            Some(newline) if newline > 0 => newline + 1,
+            _ => limit,
+        };
+
+        let mut result = rest[..end].to_string();
+        let next = start + end;
+        result.write_fmt(format_args!(
+            "\n[Output truncated: showing characters {}-{next} of {}; {} more omitted. \
+             Repeat the call with `cursor: \"{}\"` for the next page.]\n",
+            start + 1,
+            text.len(),
+            text.len() - next,
+            self.cursor_at(next)
+        ));
+        result
+    }
+}
+
+/// Hash the arguments that determine a response's full content
+fn fingerprint(args: &impl Serialize) -> Result<u64> {
+    let mut value = serde_json::to_value(args)?;
+    if let Some(object) = value.as_object_mut() {
+        for key in PAGE_ARGUMENTS {
+            object.remove(*key);
+        }
+    }
+
+    let mut hasher = FxHasher::default();
+    value.to_string().hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+fn decode_cursor(cursor: &str, fingerprint: u64) -> Result<usize> {
+    let invalid = || anyhow!("Invalid cursor `{cursor}`. Use the cursor exactly as returned.");
+
+    let (offset, cursor_fingerprint) = cursor.trim().split_once('.').ok_or_else(invalid)?;
+    let offset = offset.parse().map_err(|_| invalid())?;
+    let cursor_fingerprint = u64::from_str_radix(cursor_fingerprint, 16).map_err(|_| invalid())?;
+
+    if cursor_fingerprint != fingerprint {
+        bail!(
+            "Cursor `{cursor}` belongs to a different request. Repeat the original call with the \
+             same arguments, changing only `cursor`."
+        );
+    }
+
+    Ok(offset)
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn select_respects_budgets() {
+        let args = json!({ "name": "crate" });
+        let page = Page::new(&args, Some(2), None, None).unwrap();
+        assert_eq!(page.select([10, 10, 10]), 0..2);
+
+        let page = Page::new(&args, None, Some(25), None).unwrap();
+        assert_eq!(page.select([10, 10, 10]), 0..2);
+
+        // an entry larger than the whole budget is still shown on its own
+        let page = Page::new(&args, None, Some(5), None).unwrap();
+        assert_eq!(page.select([10, 10, 10]), 0..1);
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        let args = json!({ "name": "crate", "max_items": 2 });
+        let page = Page::new(&args, Some(2), None, None).unwrap();
+        let shown = page.select([1, 1, 1, 1, 1]);
+        let note = page.omission_note(&shown, 5, "items", None).unwrap();
+        assert!(note.contains("Showing items 1-2 of 5; 3 more omitted"));
+
+        let cursor = page.cursor_at(shown.end);
+        let args = json!({ "name": "crate", "max_items": 2, "cursor": cursor });
+        let next = Page::new(&args, Some(2), None, Some(&cursor)).unwrap();
+        assert_eq!(next.select([1, 1, 1, 1, 1]), 2..4);
+
+        let other = json!({ "name": "other" });
+        assert!(Page::new(&other, None, None, Some(&cursor)).is_err());
+        assert!(Page::new(&args, None, None, Some("garbage")).is_err());
+    }
+
+    #[test]
+    fn truncate_text_breaks_at_lines() {
+        let args = json!({});
+        let text = "line one\nline two\nline three\n";
+        let page = Page::new(&args, None, Some(12), None).unwrap();
+        let truncated = page.truncate_text(text);
+        assert!(truncated.starts_with("line one\n\n[Output truncated"));
+
+        let next = Page::new(&args, None, Some(12), Some(&page.cursor_at(9))).unwrap();
+        assert!(next.truncate_text(text).starts_with("line two\n\n"));
+    }
+}
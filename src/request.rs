@@ -1,16 +1,34 @@
 use crate::doc_ref::DocRef;
 use crate::indent::Indent;
-use crate::rustdoc::{RustdocData, RustdocProject};
+use crate::resource_uri::{ResourceUri, release};
+use crate::rustdoc::{LoadError, RustdocData, RustdocProject};
 use crate::string_utils::case_aware_jaro_winkler;
+use anyhow::{Result, bail};
 use elsa::FrozenMap;
 use fieldwork::Fieldwork;
-use rustdoc_types::{Id, Item, ItemEnum};
+use rustdoc_types::{ExternalCrate, Id, Item, ItemEnum, ItemKind};
+use serde::Serialize;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::rc::Rc;
 
+pub(crate) use callable::CallableMethod;
+pub(crate) use implements::{Bindings, Explanation, TypeExpr, Verdict};
+
+mod alias;
+mod callable;
+mod deref;
 mod formatting;
+mod implements;
+mod primitives;
+mod qualified_path;
+mod substitution;
+mod suggestions;
+
+/// How closely the name of a trait impl's item must match the segment that was not found to be
+/// suggested
+const MIN_TRAIT_IMPL_MEMBER_SCORE: f64 = 0.9;
 
 /// Represents a single request with its own cache and state
 pub(crate) struct Request {
@@ -18,6 +36,9 @@ pub(crate) struct Request {
 
     // Request-scoped cache
     crate_cache: FrozenMap<String, Box<RustdocData>>,
+
+    // Crates that failed to load during this request, so we neither retry nor lose the reason
+    load_errors: FrozenMap<String, Box<LoadError>>,
 }
 
 impl Debug for Request {
@@ -25,6 +46,7 @@ impl Debug for Request {
         f.debug_struct("Request")
             .field("project", &self.project)
             .field("crate_cache (len)", &self.crate_cache.len())
+            .field("load_errors (len)", &self.load_errors.len())
             .finish()
     }
 }
@@ -34,6 +56,7 @@ impl Request {
     pub(crate) fn new(project: Rc<RustdocProject>) -> Self {
         Self {
             crate_cache: FrozenMap::new(),
+            load_errors: FrozenMap::new(),
             project,
         }
     }
@@ -44,6 +67,13 @@ impl Request {
         path: &str,
         suggestions: &mut Vec<Suggestion<'a>>,
     ) -> Option<DocRef<'a, Item>> {
+        if path.starts_with('<') {
+            return self.resolve_qualified_path(path, suggestions);
+        }
+        if let Some((self_path, trait_path)) = path.split_once(" as ") {
+            return self.resolve_impl(self_path, trait_path, suggestions);
+        }
+
         let (crate_name, index) = if let Some(index) = path.find("::") {
             (&path[..index], Some(index + 2))
         } else {
@@ -51,6 +81,14 @@ impl Request {
         };
 
         let Some(crate_data) = self.load(crate_name) else {
+            if let Some(primitive) = self.primitive(crate_name) {
+                return match index {
+                    Some(index) => {
+                        self.find_children_recursive(primitive, path, index, suggestions)
+                    }
+                    None => Some(primitive),
+                };
+            }
             suggestions.extend(self.project.available_crates().map(|name| Suggestion {
                 path: name.to_string(),
                 item: None,
@@ -93,7 +131,7 @@ impl Request {
             &path[next_segment_start..]
         );
 
-        for child in item.child_items() {
+        for child in item.child_items().chain(item.members()) {
             if let Some(name) = child.name()
                 && name == segment
                 && let Some(child) =
@@ -103,6 +141,26 @@ impl Request {
             }
         }
 
+        // items of the type an alias stands for, like `Db::connect` for `type Db = Pool<Postgres>`
+        if let Some(target) = self.alias_target(item, None)
+            && let Some(child) =
+                self.find_children_recursive(target.item(), path, index, suggestions)
+        {
+            return Some(child);
+        }
+
+        // methods of the types this one derefs to, like `String::len` from `str`
+        for target in self.deref_targets(item) {
+            for method in target.methods() {
+                if method.name() == Some(segment)
+                    && let Some(child) =
+                        self.find_children_recursive(method, path, next_segment_start, suggestions)
+                {
+                    return Some(child);
+                }
+            }
+        }
+
         suggestions.extend(self.generate_suggestions(item, path, index));
         None
     }
@@ -113,37 +171,207 @@ impl Request {
         path: &str,
         index: usize,
     ) -> impl Iterator<Item = Suggestion<'a>> {
-        item.child_items().filter_map(move |item| {
-            item.name().and_then(|name| {
-                let full_path = format!("{}{name}", &path[..index]);
-                if path.starts_with(&full_path) {
-                    None
-                } else {
-                    let score = case_aware_jaro_winkler(path, &full_path);
-                    Some(Suggestion {
-                        path: full_path,
-                        score,
-                        item: Some(item),
-                    })
-                }
+        let segment = path[index..].split("::").next().unwrap_or_default();
+        // every type picks up `fmt`, `clone` and the like from its trait impls, so those are only
+        // suggested for a segment that is nearly their name
+        let from_trait_impls = item.trait_impl_members().filter(move |member| {
+            member.name().is_some_and(|name| {
+                case_aware_jaro_winkler(segment, name) >= MIN_TRAIT_IMPL_MEMBER_SCORE
+            })
+        });
+        item.child_items()
+            .chain(item.own_members())
+            .chain(from_trait_impls)
+            .filter_map(move |item| {
+                item.name().and_then(|name| {
+                    let full_path = format!("{}{name}", &path[..index]);
+                    if path.starts_with(&full_path) {
+                        None
+                    } else {
+                        let score = case_aware_jaro_winkler(path, &full_path);
+                        Some(Suggestion {
+                            path: full_path,
+                            score,
+                            item: Some(item),
+                        })
+                    }
+                })
             })
-        })
     }
 
     fn load(&self, crate_name: &str) -> Option<&RustdocData> {
         let crate_name = self.project.normalize_crate_name(crate_name)?;
-        match self.crate_cache.get(&*crate_name) {
-            Some(docs) => Some(docs),
-            None => {
-                let crate_data = self.project.load_crate(crate_name)?;
-                Some(
-                    self.crate_cache
-                        .insert(crate_name.to_string(), Box::new(crate_data)),
-                )
+        let key = self.project.crate_key(crate_name);
+        if let Some(docs) = self.crate_cache.get(&key) {
+            return Some(docs);
+        }
+
+        if self.load_errors.get(&key).is_some() {
+            return None;
+        }
+
+        match self.project.load_crate(crate_name) {
+            Ok(Some(crate_data)) => Some(self.crate_cache.insert(key, Box::new(crate_data))),
+            Ok(None) => None,
+            Err(error) => {
+                log::warn!("{error}");
+                self.load_errors.insert(key, Box::new(error));
+                None
+            }
+        }
+    }
+
+    /// Load the crate an `external_crates` entry names, see
+    /// [`RustdocProject::load_external_crate`]
+    fn load_external(&self, external: &ExternalCrate) -> Option<&RustdocData> {
+        let version = external.html_root_url.as_deref().and_then(docs_version);
+        let key = self.project.external_crate_key(&external.name, version);
+        if let Some(docs) = self.crate_cache.get(&key) {
+            return Some(docs);
+        }
+        if self.load_errors.get(&key).is_some() {
+            return None;
+        }
+
+        match self.project.load_external_crate(&external.name, version) {
+            Ok(Some(crate_data)) => Some(self.crate_cache.insert(key, Box::new(crate_data))),
+            Ok(None) => None,
+            Err(error) => {
+                log::warn!("{error}");
+                self.load_errors.insert(key, Box::new(error));
+                None
             }
         }
     }
 
+    /// Find an item of another crate by an id from `crate_docs`, like the source of a re-export
+    ///
+    /// Such ids only mean something to `crate_docs`: its `paths` table gives the item's path,
+    /// and its `external_crates` table the crate to look for that path in.
+    pub(crate) fn get_external<'a>(
+        &'a self,
+        crate_docs: &RustdocData,
+        id: &Id,
+    ) -> Option<DocRef<'a, Item>> {
+        let summary = crate_docs.paths.get(id)?;
+        if summary.crate_id == 0 {
+            return None;
+        }
+        let external = crate_docs.external_crates.get(&summary.crate_id)?;
+        let external_docs = self.load_external(external)?;
+
+        // the path's first segment is the crate's name, which is not always the one it is
+        // loaded under, so the rest of the path is looked up from the crate's root
+        let (_, item_path) = summary.path.split_first()?;
+        let root = external_docs.get(self, &external_docs.root)?;
+        let path = format!("{}::{}", external_docs.name(), item_path.join("::"));
+        let index = external_docs.name().len() + 2;
+        self.find_children_recursive(root, &path, index, &mut vec![])
+            .filter(|item| item_path.is_empty() || item.kind() == summary.kind)
+            .or_else(|| {
+                // items in private modules are only found by their path where they are defined
+                external_docs
+                    .paths
+                    .iter()
+                    .find(|(_, other)| {
+                        other.crate_id == 0
+                            && other.kind == summary.kind
+                            && other.path.get(1..) == Some(item_path)
+                    })
+                    .and_then(|(id, _)| external_docs.get(self, id))
+            })
+    }
+
+    /// The root module of a crate whose docs are already built, without building them
+    pub(crate) fn built_crate_root(&self, crate_name: &str) -> Option<DocRef<'_, Item>> {
+        let crate_docs = self.load_built(crate_name)?;
+        crate_docs.get(self, &crate_docs.root)
+    }
+
+    /// Load a crate only if its docs are already built, see [`RustdocProject::load_built_crate`]
+    fn load_built(&self, crate_name: &str) -> Option<&RustdocData> {
+        let crate_name = self.project.normalize_crate_name(crate_name)?;
+        let key = self.project.crate_key(crate_name);
+        if let Some(docs) = self.crate_cache.get(&key) {
+            return Some(docs);
+        }
+        let crate_data = self.project.load_built_crate(crate_name)?;
+        Some(self.crate_cache.insert(key, Box::new(crate_data)))
+    }
+
+    /// The reason a crate could not be loaded during this request, if it failed
+    ///
+    /// Path lookups that fail because documentation could not be built should report this
+    /// instead of suggesting alternative spellings.
+    pub(crate) fn load_error(&self, path: &str) -> Option<&LoadError> {
+        let crate_name = path.split("::").next()?;
+        let crate_name = self.project.normalize_crate_name(crate_name)?;
+        self.load_errors.get(&self.project.crate_key(crate_name))
+    }
+
+    /// The resource URI for an item path, naming the crate and version it resolves to
+    pub(crate) fn resource_uri(&self, path: &str) -> Option<ResourceUri> {
+        // impls and qualified paths like `<T as Trait>::item` have no URI
+        if path.starts_with('<') || path.contains(" as ") {
+            return None;
+        }
+        let mut segments = path.split("::");
+        let crate_data = self.load(segments.next()?)?;
+        Some(ResourceUri::new(
+            crate_data.name(),
+            crate_data.crate_version.as_deref(),
+            segments.map(str::to_string).collect(),
+        ))
+    }
+
+    /// The resource URI for a resolved item, from its canonical path so that re-exports and
+    /// aliased crate names share one URI
+    ///
+    /// Items rustdoc records no path for, like methods and fields, fall back to the path they were
+    /// requested by.
+    pub(crate) fn item_uri(&self, item: DocRef<'_, Item>, requested: &str) -> Option<ResourceUri> {
+        let Some(path) = item.path() else {
+            return self.resource_uri(requested);
+        };
+        let crate_docs = item.crate_docs();
+        Some(ResourceUri::new(
+            crate_docs.name(),
+            crate_docs.crate_version.as_deref(),
+            path.segments().iter().skip(1).cloned().collect(),
+        ))
+    }
+
+    /// Resolve a resource URI, checking that it refers to the version of the crate in this project
+    pub(crate) fn resolve_uri<'a>(
+        &'a self,
+        uri: &ResourceUri,
+        suggestions: &mut Vec<Suggestion<'a>>,
+    ) -> Result<Option<DocRef<'a, Item>>> {
+        if let Some(requested) = uri.version()
+            && let Some(crate_data) = self.load(uri.crate_name())
+            && let Some(available) = crate_data.crate_version.as_deref()
+            && requested != release(available)
+        {
+            bail!(
+                "`{uri}` refers to {} {requested}, but this project uses version {available}",
+                uri.crate_name()
+            );
+        }
+
+        Ok(self.resolve_path(&uri.item_path(), suggestions))
+    }
+
+    /// Whether an id path from a search index ends at a `use` item, rather than at the item
+    /// it re-exports
+    pub(crate) fn is_reexport(&self, crate_name: &str, ids: &[u32]) -> bool {
+        let Some((last, _)) = ids.split_last() else {
+            return false;
+        };
+        self.load(crate_name)
+            .and_then(|crate_docs| crate_docs.index.get(&Id(*last)))
+            .is_some_and(|item| matches!(item.inner, ItemEnum::Use(_)))
+    }
+
     pub(crate) fn get_item_from_id_path<'a>(
         &'a self,
         crate_name: &str,
@@ -156,10 +384,7 @@ impl Request {
         for id in ids {
             item = item.get(&Id(*id))?;
             if let ItemEnum::Use(use_item) = item.inner() {
-                item = use_item
-                    .id
-                    .and_then(|id| item.get(&id))
-                    .or_else(|| item.request().resolve_path(&use_item.source, &mut vec![]))?;
+                item = item.build_ref(use_item).source_item()?;
                 if !use_item.is_glob {
                     item.set_name(&use_item.name);
                 }
@@ -190,3 +415,44 @@ pub(crate) struct Suggestion<'a> {
     item: Option<DocRef<'a, Item>>,
     score: f64,
 }
+
+/// JSON response for a path that could not be resolved
+#[derive(Debug, Serialize)]
+pub(crate) struct NotFoundJson<'a> {
+    not_found: &'a str,
+    suggestions: Vec<SuggestionJson>,
+}
+
+#[derive(Debug, Serialize)]
+struct SuggestionJson {
+    path: String,
+    /// Item kind, or `None` if the suggestion is a crate
+    kind: Option<ItemKind>,
+}
+
+impl<'a> NotFoundJson<'a> {
+    /// Keep the same suggestions the text output shows: the five best, if they are close enough
+    pub(crate) fn new(not_found: &'a str, mut suggestions: Vec<Suggestion<'_>>) -> Self {
+        suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
+        let suggestions = suggestions
+            .into_iter()
+            .take(5)
+            .filter(|s| s.score() > 0.8)
+            .map(|suggestion| SuggestionJson {
+                kind: suggestion.item().map(|item| item.kind()),
+                path: suggestion.path,
+            })
+            .collect();
+        Self {
+            not_found,
+            suggestions,
+        }
+    }
+}
+
+/// The version in a docs URL like `https://docs.rs/http/1.1.0/`, if it names one
+fn docs_version(html_root_url: &str) -> Option<&str> {
+    html_root_url
+        .split('/')
+        .find(|segment| segment.starts_with(|c: char| c.is_ascii_digit()) && segment.contains('.'))
+}
@@ -0,0 +1,97 @@
+//! Seeing through type aliases to the types they stand for, e.g. from `io::Result<T>` to
+//! `Result<T, io::Error>`
+
+use super::Request;
+use super::implements::{Bindings, TypeExpr, substitute};
+use crate::doc_ref::DocRef;
+use fieldwork::Fieldwork;
+use rustdoc_types::{GenericParamDefKind, Item, ItemEnum, Type};
+
+/// How many aliases of aliases to follow, e.g. `type A = B<u8>; type B<T> = Vec<T>;`
+const MAX_ALIAS_DEPTH: usize = 8;
+
+/// The type a type alias stands for
+#[derive(Debug, Clone, Fieldwork)]
+#[fieldwork(get)]
+pub(crate) struct AliasTarget<'a> {
+    /// The aliased type, with the alias's type parameters replaced by the arguments it was given,
+    /// or left as they are named where it was not
+    type_: TypeExpr,
+    /// The documented type the alias refers to
+    #[field(copy)]
+    item: DocRef<'a, Item>,
+}
+
+impl Request {
+    /// The type an alias stands for, following aliases of aliases
+    ///
+    /// `concrete_type` is the alias with arguments, like `io::Result<u8>`. Aliases of types
+    /// without docs, like a function pointer or a type parameter, have no target.
+    pub(crate) fn alias_target<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        concrete_type: Option<&TypeExpr>,
+    ) -> Option<AliasTarget<'a>> {
+        let mut target = self.alias_step(item, concrete_type)?;
+        for _ in 0..MAX_ALIAS_DEPTH {
+            if !matches!(target.item.inner(), ItemEnum::TypeAlias(_)) {
+                return Some(target);
+            }
+            target = self.alias_step(target.item, Some(&target.type_))?;
+        }
+        None
+    }
+
+    /// The type one alias stands for, which may be another alias
+    fn alias_step<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        concrete_type: Option<&TypeExpr>,
+    ) -> Option<AliasTarget<'a>> {
+        let ItemEnum::TypeAlias(type_alias) = item.inner() else {
+            return None;
+        };
+        let args = match concrete_type {
+            Some(TypeExpr::Path { args, .. }) => args.as_slice(),
+            _ => &[],
+        };
+
+        let mut args = args.iter();
+        let mut bindings = Bindings::new();
+        for param in &type_alias.generics.params {
+            let GenericParamDefKind::Type { default, .. } = &param.kind else {
+                continue;
+            };
+            let bound = match (args.next(), default) {
+                (Some(arg), _) => arg.clone(),
+                (None, Some(default)) => substitute(item, default, &bindings)?,
+                (None, None) => TypeExpr::Path {
+                    path: param.name.clone(),
+                    args: vec![],
+                },
+            };
+            bindings.insert(param.name.clone(), bound);
+        }
+
+        Some(AliasTarget {
+            type_: substitute(item, &type_alias.type_, &bindings)?,
+            item: self.documented_type(item, &type_alias.type_)?,
+        })
+    }
+
+    /// The item documenting a type that appears in `item`'s signature, e.g. `String` for
+    /// `alloc::string::String`, or the `slice` primitive for `[T]`
+    pub(super) fn documented_type<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        type_: &Type,
+    ) -> Option<DocRef<'a, Item>> {
+        match type_ {
+            Type::ResolvedPath(path) => item.get_or_external(&path.id),
+            Type::Primitive(name) => self.primitive(name),
+            Type::Slice(_) => self.primitive("slice"),
+            Type::Array { .. } => self.primitive("array"),
+            _ => None,
+        }
+    }
+}
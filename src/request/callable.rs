@@ -0,0 +1,205 @@
+use super::Request;
+use super::implements::{TypeExpr, Verdict};
+use super::substitution::with_default_args;
+use crate::doc_ref::DocRef;
+use fieldwork::Fieldwork;
+use rustc_hash::FxHashSet;
+use rustdoc_types::{Item, ItemEnum, Type};
+use serde::Serialize;
+
+/// A method that can be called on a value with method call syntax, and where it comes from
+#[derive(Debug, Serialize, Fieldwork)]
+#[fieldwork(get)]
+pub(crate) struct CallableMethod<'a> {
+    #[field(copy)]
+    name: &'a str,
+    /// How the method takes its receiver: `self`, `&self`, `&mut self`, or a typed `self` like
+    /// `self: Box<Self>`
+    receiver: String,
+    signature: String,
+    /// Full path of the trait that provides the method, which must be in scope to call it
+    #[serde(rename = "trait", skip_serializing_if = "Option::is_none")]
+    trait_path: Option<String>,
+    /// The `Deref` target the method is found on, if it is not on the type itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deref_target: Option<String>,
+    /// Whether the impl only applies for some type arguments, which the query left open
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    #[field(copy)]
+    conditional: bool,
+}
+
+impl Request {
+    /// Every method that method call syntax finds on a type, in the order the compiler looks
+    /// for them: the type's inherent methods, then its traits' methods, then the same for each
+    /// type it derefs to
+    ///
+    /// Blanket impls the type's docs list are included with its traits. `blanket_crates` names
+    /// further crates whose blanket impls, like itertools' `impl<T: Iterator> Itertools for T`,
+    /// are checked against the type. A method found again through a deref, like `clone`, is only
+    /// listed the first time.
+    pub(crate) fn callable_methods<'a>(
+        &'a self,
+        type_: &TypeExpr,
+        blanket_crates: &[String],
+    ) -> Option<Vec<CallableMethod<'a>>> {
+        let item = self.type_item(type_)?;
+        let type_ = &with_default_args(item, type_);
+        let mut methods = vec![];
+        let mut seen = FxHashSet::default();
+
+        self.add_callable_methods(item, Some(type_), None, &mut seen, &mut methods);
+        for crate_name in blanket_crates {
+            let Some(crate_docs) = self.load(crate_name) else {
+                continue;
+            };
+            for impl_item in crate_docs.index.values() {
+                if let ItemEnum::Impl(impl_data) = &impl_item.inner
+                    && impl_data.blanket_impl.is_some()
+                    && !impl_data.is_negative
+                    && let impl_block = DocRef::new(self, crate_docs, impl_item)
+                    && let Some(explanation) = self.check_impl(impl_block, type_, 0)
+                    && explanation.verdict() != Verdict::NotImplemented
+                {
+                    let conditional = explanation.verdict() == Verdict::Unknown;
+                    self.add_impl_methods(impl_block, None, conditional, &mut seen, &mut methods);
+                }
+            }
+        }
+
+        for target in self.deref_targets(item) {
+            let target_name = self.format_type(target.target());
+            self.add_callable_methods(
+                target.item(),
+                None,
+                Some(&target_name),
+                &mut seen,
+                &mut methods,
+            );
+        }
+
+        Some(methods)
+    }
+
+    /// Add a type's inherent methods and the methods of its trait impls
+    ///
+    /// With the type's arguments, impls whose bounds they do not meet are left out, like
+    /// `Clone` for `Vec<T>` where `T` is not `Clone`, and those that depend on arguments the
+    /// type leaves open are marked conditional.
+    fn add_callable_methods<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        type_: Option<&TypeExpr>,
+        deref_target: Option<&str>,
+        seen: &mut FxHashSet<(&'a str, Option<String>)>,
+        methods: &mut Vec<CallableMethod<'a>>,
+    ) {
+        for method in item.methods() {
+            self.add_method(method, None, deref_target, false, seen, methods);
+        }
+        // the type's own impls before blanket impls, each by trait path
+        let mut impls = item
+            .traits()
+            .filter_map(|impl_block| match impl_block.inner() {
+                ItemEnum::Impl(impl_data) if !impl_data.is_negative => {
+                    let verdict = match type_ {
+                        Some(type_) => self.check_impl(impl_block, type_, 0)?.verdict(),
+                        None => Verdict::Implemented,
+                    };
+                    (verdict != Verdict::NotImplemented).then_some((
+                        impl_data.blanket_impl.is_some(),
+                        self.impl_trait_path(impl_block)?,
+                        impl_block,
+                        verdict == Verdict::Unknown,
+                    ))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        impls.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+        for (_, _, impl_block, conditional) in impls {
+            self.add_impl_methods(impl_block, deref_target, conditional, seen, methods);
+        }
+    }
+
+    fn add_impl_methods<'a>(
+        &'a self,
+        impl_block: DocRef<'a, Item>,
+        deref_target: Option<&str>,
+        conditional: bool,
+        seen: &mut FxHashSet<(&'a str, Option<String>)>,
+        methods: &mut Vec<CallableMethod<'a>>,
+    ) {
+        let trait_path = self.impl_trait_path(impl_block);
+        for method in impl_block.impl_items() {
+            self.add_method(
+                method,
+                trait_path.clone(),
+                deref_target,
+                conditional,
+                seen,
+                methods,
+            );
+        }
+    }
+
+    /// Full path of the trait an impl implements
+    fn impl_trait_path(&self, impl_block: DocRef<'_, Item>) -> Option<String> {
+        let ItemEnum::Impl(impl_data) = impl_block.inner() else {
+            return None;
+        };
+        let trait_ = impl_data.trait_.as_ref()?;
+        Some(
+            impl_block
+                .crate_docs()
+                .path(&trait_.id)
+                .map(|path| path.to_string())
+                .unwrap_or_else(|| trait_.path.clone()),
+        )
+    }
+
+    /// Add a method if it takes `self` and was not already found
+    fn add_method<'a>(
+        &'a self,
+        method: DocRef<'a, Item>,
+        trait_path: Option<String>,
+        deref_target: Option<&str>,
+        conditional: bool,
+        seen: &mut FxHashSet<(&'a str, Option<String>)>,
+        methods: &mut Vec<CallableMethod<'a>>,
+    ) {
+        let (Some(name), ItemEnum::Function(function)) = (method.name(), method.inner()) else {
+            return;
+        };
+        let Some(("self", self_type)) = function
+            .sig
+            .inputs
+            .first()
+            .map(|(name, type_)| (name.as_str(), type_))
+        else {
+            return;
+        };
+        if !seen.insert((name, trait_path.clone())) {
+            return;
+        }
+
+        let receiver = match self_type {
+            Type::Generic(self_) if self_ == "Self" => "self".to_string(),
+            Type::BorrowedRef {
+                is_mutable, type_, ..
+            } if matches!(&**type_, Type::Generic(self_) if self_ == "Self") => {
+                if *is_mutable { "&mut self" } else { "&self" }.to_string()
+            }
+            other => format!("self: {}", self.format_type(other)),
+        };
+
+        methods.push(CallableMethod {
+            name,
+            receiver,
+            signature: self.format_function_signature(name, function),
+            trait_path,
+            deref_target: deref_target.map(str::to_string),
+            conditional,
+        });
+    }
+}
@@ -0,0 +1,86 @@
+use super::Request;
+use super::qualified_path::trait_impls_named;
+use crate::doc_ref::DocRef;
+use fieldwork::Fieldwork;
+use rustdoc_types::{Item, ItemEnum, Type};
+
+/// How many `Deref` impls to follow from a type, e.g. `Box<String>` to `String` to `str`
+const MAX_DEREF_DEPTH: usize = 8;
+
+/// A type reached through a `Deref` impl, whose methods can be called on the type that derefs to it
+#[derive(Debug, Clone, Copy, Fieldwork)]
+#[fieldwork(get)]
+pub(crate) struct DerefTarget<'a> {
+    /// The `Target` type as the impl writes it, e.g. `[T]`
+    #[field(copy)]
+    target: &'a Type,
+    /// The documented type the target refers to
+    #[field(copy)]
+    item: DocRef<'a, Item>,
+}
+
+impl<'a> DerefTarget<'a> {
+    /// The target's inherent methods that take `self`, which are the ones a deref makes callable
+    pub(crate) fn methods(&self) -> impl Iterator<Item = DocRef<'a, Item>> + use<'a> {
+        self.item.methods().filter(|method| {
+            matches!(
+                method.inner(),
+                ItemEnum::Function(function)
+                    if function.sig.inputs.first().is_some_and(|(name, _)| name == "self")
+            )
+        })
+    }
+}
+
+impl Request {
+    /// The types an item derefs to, following `Deref` impls from each target in turn
+    ///
+    /// A `Target` that is a type parameter, like the `T` of `Box<T>`, cannot be followed and ends
+    /// the chain.
+    pub(crate) fn deref_targets<'a>(&'a self, item: DocRef<'a, Item>) -> Vec<DerefTarget<'a>> {
+        let mut targets: Vec<DerefTarget<'a>> = vec![];
+        let mut current = item;
+        while targets.len() < MAX_DEREF_DEPTH
+            && let Some(target) = self.deref_target(current)
+        {
+            let seen = |other: DocRef<'a, Item>| {
+                other.id == target.item.id
+                    && other.crate_docs().name() == target.item.crate_docs().name()
+            };
+            if seen(item) || targets.iter().any(|previous| seen(previous.item)) {
+                break;
+            }
+            current = target.item;
+            targets.push(target);
+        }
+        targets
+    }
+
+    /// The `Target` of an item's `Deref` impl, if it is a type with docs
+    fn deref_target<'a>(&'a self, item: DocRef<'a, Item>) -> Option<DerefTarget<'a>> {
+        if !matches!(
+            item.inner(),
+            ItemEnum::Struct(_) | ItemEnum::Enum(_) | ItemEnum::Union(_) | ItemEnum::Primitive(_)
+        ) {
+            return None;
+        }
+        let impl_block = trait_impls_named(item, "Deref").next()?;
+        let target =
+            impl_block
+                .impl_items()
+                .into_iter()
+                .find_map(|impl_item| match impl_item.inner() {
+                    ItemEnum::AssocType {
+                        type_: Some(type_), ..
+                    } if impl_item.name() == Some("Target") => Some(type_),
+                    _ => None,
+                })?;
+
+        let target_item = self.documented_type(impl_block, target)?;
+
+        Some(DerefTarget {
+            target,
+            item: target_item,
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use rustdoc_types::{AssocItemConstraint, AssocItemConstraintKind, TraitBoundModifier};
 
 use super::*;
+use crate::request::substitution::substitute_function;
 
 impl Request {
     /// Format a function signature
@@ -14,8 +15,25 @@ impl Request {
         self.format_function_signature(name, &function)
     }
 
+    /// A method's signature, with its impl's type parameters replaced by what they stand for in
+    /// the concrete type being shown, if there is one
+    pub(super) fn method_signature(
+        &self,
+        item: DocRef<'_, Item>,
+        name: &str,
+        function: &Function,
+        context: &FormatContext,
+    ) -> String {
+        match context.substitutions().get(&item.id) {
+            Some(bindings) => {
+                self.format_function_signature(name, &substitute_function(function, bindings))
+            }
+            None => self.format_function_signature(name, function),
+        }
+    }
+
     /// Format a function signature
-    pub(super) fn format_function_signature(&self, name: &str, func: &Function) -> String {
+    pub(crate) fn format_function_signature(&self, name: &str, func: &Function) -> String {
         let mut sig = String::new();
 
         // Add function modifiers in the correct order
@@ -338,7 +356,7 @@ impl Request {
     }
 
     /// Format a path
-    pub(super) fn format_path(&self, path: &Path) -> String {
+    pub(crate) fn format_path(&self, path: &Path) -> String {
         let mut result = path.path.clone();
         if let Some(args) = &path.args {
             result.push_str(&self.format_generic_args(args));
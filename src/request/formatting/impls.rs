@@ -1,20 +1,48 @@
 use rustdoc_types::ItemKind;
 
 use super::*;
+use crate::iterators::InherentImplBlockIter;
+use crate::request::Bindings;
 use crate::rustdoc::RUST_CRATES;
+use rustc_hash::FxHashMap;
 use std::cmp::Ordering;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum TraitCategory {
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum TraitCategory {
     CrateLocal, // From current crate/workspace (most relevant)
     External,   // Third-party crates
     Std,        // std/core/alloc (least relevant, usually noise)
+    Auto,       // Send, Sync, Unpin and other auto traits, implemented or not
+    Blanket,    // Blanket impls like `impl<T> From<T> for T`
 }
 
+/// Std traits the compiler implements automatically, which types can also opt out of
+const AUTO_TRAITS: &[&str] = &[
+    "Send",
+    "Sync",
+    "Unpin",
+    "UnwindSafe",
+    "RefUnwindSafe",
+    "Freeze",
+    "UnsafeUnpin",
+];
+
 #[derive(Debug)]
-struct TraitImpl {
-    name: String,
-    category: TraitCategory,
+pub(super) struct TraitImpl {
+    pub(super) name: String,
+    pub(super) category: TraitCategory,
+}
+
+/// A type's inherent methods and trait impls, narrowed to the impls that apply to the concrete
+/// type being shown, if there is one
+pub(super) struct AssociatedItems<'a> {
+    pub(super) methods: Vec<DocRef<'a, Item>>,
+    pub(super) trait_impls: Vec<DocRef<'a, Item>>,
+    /// What the type parameters of each method's impl stand for in the concrete type
+    pub(super) substitutions: FxHashMap<Id, Bindings>,
+    /// How many impl blocks the concrete type does not meet the bounds of
+    pub(super) omitted_impls: usize,
 }
 
 impl Request {
@@ -25,25 +53,101 @@ impl Request {
         context: &FormatContext,
     ) -> String {
         let mut result = String::new();
+        let AssociatedItems {
+            methods: inherent_methods,
+            trait_impls,
+            substitutions,
+            omitted_impls,
+        } = self.associated_items(item, context);
 
-        let inherent_methods = item.methods().collect::<Vec<_>>();
         // Show inherent methods first
         if !inherent_methods.is_empty() {
-            result.push_str(&self.format_item_list(inherent_methods, "Associated Types", context));
+            let context = context.clone().with_substitutions(substitutions);
+            result.push_str(&self.format_item_list(inherent_methods, "Associated Types", &context));
+        }
+
+        for target in self.deref_targets(item) {
+            let deref_methods = target.methods().collect::<Vec<_>>();
+            if !deref_methods.is_empty() {
+                let title = format!(
+                    "Methods from Deref<Target = {}>",
+                    self.format_type(target.target())
+                );
+                result.push_str(&self.format_item_list(deref_methods, &title, context));
+            }
         }
 
-        let trait_impls = item.traits().collect::<Vec<_>>();
         // Show trait implementations
         if !trait_impls.is_empty() {
-            let formatted_traits = self.format_trait_implementations(&trait_impls, context);
+            let formatted_traits = if context.impl_details() {
+                self.format_trait_implementation_details(&trait_impls)
+            } else {
+                self.format_trait_implementations(&trait_impls, context)
+            };
             if !formatted_traits.is_empty() {
                 result.write_fmt(format_args!("\n{formatted_traits}"));
             }
         }
 
+        if let Some(concrete_type) = context.concrete_type()
+            && omitted_impls > 0
+        {
+            let (blocks, their) = if omitted_impls == 1 {
+                ("block", "its")
+            } else {
+                ("blocks", "their")
+            };
+            result.write_fmt(format_args!(
+                "\n{omitted_impls} impl {blocks} left out, as `{concrete_type}` does not meet \
+                 {their} bounds\n"
+            ));
+        }
+
         result
     }
 
+    /// The inherent methods and trait impls of a type, leaving out the impl blocks whose bounds
+    /// the concrete type in the context does not meet
+    pub(super) fn associated_items<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        context: &FormatContext,
+    ) -> AssociatedItems<'a> {
+        let Some(concrete_type) = context.concrete_type() else {
+            return AssociatedItems {
+                methods: item.methods().collect(),
+                trait_impls: item.traits().collect(),
+                substitutions: FxHashMap::default(),
+                omitted_impls: 0,
+            };
+        };
+
+        let mut associated = AssociatedItems {
+            methods: vec![],
+            trait_impls: vec![],
+            substitutions: FxHashMap::default(),
+            omitted_impls: 0,
+        };
+        for impl_block in InherentImplBlockIter::new(item) {
+            let Some(bindings) = self.applicable_impl(impl_block, concrete_type) else {
+                associated.omitted_impls += 1;
+                continue;
+            };
+            for method in impl_block.impl_items() {
+                associated.substitutions.insert(method.id, bindings.clone());
+                associated.methods.push(method);
+            }
+        }
+        for impl_block in item.traits() {
+            if self.applicable_impl(impl_block, concrete_type).is_some() {
+                associated.trait_impls.push(impl_block);
+            } else {
+                associated.omitted_impls += 1;
+            }
+        }
+        associated
+    }
+
     fn format_item_list(
         &self,
         mut items: Vec<DocRef<'_, Item>>,
@@ -93,7 +197,7 @@ impl Request {
 
             // For functions, show the signature inline
             if let ItemEnum::Function(inner) = &item.inner {
-                let signature = self.format_function_signature(name, inner);
+                let signature = self.method_signature(item, name, inner, context);
                 result.write_fmt(format_args!("• {visibility}{signature}\n"));
             } else {
                 result.write_fmt(format_args!("• {visibility}"));
@@ -126,23 +230,21 @@ impl Request {
         let mut crate_local = Vec::new();
         let mut external = Vec::new();
         let mut std_traits = Vec::new();
+        let mut auto_traits = Vec::new();
+        let mut blanket_impls = Vec::new();
 
         // Extract trait implementations
         for impl_block in trait_impls {
             if let ItemEnum::Impl(impl_item) = &impl_block.inner
                 && let Some(trait_path) = &impl_item.trait_
             {
-                let full_path = impl_block
-                    .crate_docs()
-                    .path(&trait_path.id)
-                    .map(|path| path.to_string())
-                    .unwrap_or(trait_path.path.clone());
-                let rendered_path = self.format_path(trait_path);
-                let impl_ = self.categorize_trait(full_path, rendered_path);
+                let impl_ = self.categorize_impl(*impl_block, impl_item, trait_path);
                 match impl_.category {
                     TraitCategory::CrateLocal => crate_local.push(impl_.name),
                     TraitCategory::External => external.push(impl_.name),
                     TraitCategory::Std => std_traits.push(impl_.name),
+                    TraitCategory::Auto => auto_traits.push(impl_.name),
+                    TraitCategory::Blanket => blanket_impls.push(impl_.name),
                 }
             }
         }
@@ -151,6 +253,8 @@ impl Request {
         crate_local.sort();
         external.sort();
         std_traits.sort();
+        auto_traits.sort_by(|a, b| a.trim_start_matches('!').cmp(b.trim_start_matches('!')));
+        blanket_impls.sort();
 
         let mut result = String::new();
         let mut sections = Vec::new();
@@ -192,6 +296,15 @@ impl Request {
             sections.push(std_section);
         }
 
+        // Listed in full, so whether a type is Send or Sync can be read off directly
+        if !auto_traits.is_empty() {
+            sections.push(format!("Auto traits: {}", auto_traits.join(", ")));
+        }
+
+        if !blanket_impls.is_empty() {
+            sections.push(format!("Blanket impls: {}", blanket_impls.join(", ")));
+        }
+
         if !sections.is_empty() {
             result = sections.join("\n");
             result.push('\n');
@@ -200,7 +313,159 @@ impl Request {
         result
     }
 
-    fn categorize_trait(&self, full_path: String, rendered_path: String) -> TraitImpl {
+    /// Format trait implementations with their headers and associated items, in the same
+    /// sections as [`Self::format_trait_implementations`]
+    fn format_trait_implementation_details(&self, trait_impls: &[DocRef<'_, Item>]) -> String {
+        let mut impls = trait_impls
+            .iter()
+            .filter_map(|impl_block| {
+                let ItemEnum::Impl(impl_item) = &impl_block.inner else {
+                    return None;
+                };
+                let trait_path = impl_item.trait_.as_ref()?;
+                let category = self
+                    .categorize_impl(*impl_block, impl_item, trait_path)
+                    .category;
+                Some((
+                    category,
+                    trait_path.path.clone(),
+                    self.format_impl_block(*impl_block, impl_item),
+                ))
+            })
+            .collect::<Vec<_>>();
+        impls.sort();
+
+        let mut result = String::new();
+        let mut previous_title = None;
+        for (category, _, block) in impls {
+            let title = match category {
+                TraitCategory::CrateLocal | TraitCategory::External => "Trait Implementations",
+                TraitCategory::Std => "std traits",
+                TraitCategory::Auto => "Auto traits",
+                TraitCategory::Blanket => "Blanket impls",
+            };
+            if previous_title != Some(title) {
+                if previous_title.is_some() {
+                    result.push('\n');
+                }
+                result.write_fmt(format_args!("{title}:\n"));
+                previous_title = Some(title);
+            }
+            result.write_fmt(format_args!("\n```rust\n{block}\n```\n"));
+        }
+        result
+    }
+
+    /// Format an impl block with its associated types and constants, e.g.
+    /// `impl Iterator for Foo { type Item = u32; }`
+    fn format_impl_block(&self, impl_block: DocRef<'_, Item>, impl_item: &Impl) -> String {
+        let mut result = self.format_impl_header(impl_item);
+        let bindings = self.impl_bindings(impl_block, impl_item);
+        if !bindings.is_empty() {
+            // a where clause ends on its own line, so the brace starts a new one
+            if impl_item.generics.where_predicates.is_empty() {
+                result.push_str(" {\n");
+            } else {
+                result.push_str("\n{\n");
+            }
+            for binding in bindings {
+                result.write_fmt(format_args!("    {binding};\n"));
+            }
+            result.push('}');
+        }
+        result
+    }
+
+    /// The associated types and constants an impl block defines, e.g. `type Item = u32`
+    pub(super) fn impl_bindings(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        impl_item: &Impl,
+    ) -> Vec<String> {
+        impl_block
+            .id_iter(&impl_item.items)
+            .filter(|item| {
+                matches!(
+                    item.inner(),
+                    ItemEnum::AssocType { .. } | ItemEnum::AssocConst { .. }
+                )
+            })
+            .filter_map(|item| self.signature(item))
+            .collect()
+    }
+
+    /// Render an impl block's header, e.g. `impl<T: Clone> From<T> for Foo`, with any where clause
+    pub(crate) fn format_impl_header(&self, impl_item: &Impl) -> String {
+        let mut header = String::new();
+        if impl_item.is_unsafe {
+            header.push_str("unsafe ");
+        }
+        header.write_fmt(format_args!(
+            "impl{} ",
+            self.format_generics(&impl_item.generics)
+        ));
+        if let Some(trait_path) = &impl_item.trait_ {
+            if impl_item.is_negative {
+                header.push('!');
+            }
+            header.write_fmt(format_args!("{} for ", self.format_path(trait_path)));
+        }
+        header.push_str(&self.format_type(&impl_item.for_));
+        header.push_str(&self.format_where_clause(&impl_item.generics.where_predicates));
+        header
+    }
+
+    /// Format a single impl block in full: its header, then every item it defines or inherits
+    /// from its trait
+    pub(super) fn format_impl(
+        &self,
+        item: DocRef<'_, Item>,
+        impl_item: &Impl,
+        context: &FormatContext,
+    ) -> String {
+        let mut result = format!("\n```rust\n{}\n```\n", self.format_impl_header(impl_item));
+        let items = item.impl_items();
+        if !items.is_empty() {
+            result.push_str(&self.format_item_list(items, "Items", context));
+        }
+        result
+    }
+
+    /// Categorize a trait impl by where its trait comes from, setting auto trait and blanket
+    /// impls apart. Negative auto trait impls are named like `!Send`.
+    pub(super) fn categorize_impl(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        impl_item: &Impl,
+        trait_path: &Path,
+    ) -> TraitImpl {
+        let full_path = impl_block
+            .crate_docs()
+            .path(&trait_path.id)
+            .map(|path| path.to_string())
+            .unwrap_or(trait_path.path.clone());
+        let impl_ = self.categorize_trait(full_path, self.format_path(trait_path));
+        let trait_name = trait_path.path.rsplit("::").next().unwrap_or_default();
+
+        if impl_item.is_synthetic
+            || (impl_.category == TraitCategory::Std && AUTO_TRAITS.contains(&trait_name))
+        {
+            let negation = if impl_item.is_negative { "!" } else { "" };
+            TraitImpl {
+                category: TraitCategory::Auto,
+                name: format!("{negation}{}", impl_.name),
+            }
+        } else if impl_item.blanket_impl.is_some() {
+            TraitImpl {
+                category: TraitCategory::Blanket,
+                ..impl_
+            }
+        } else {
+            impl_
+        }
+    }
+
+    pub(super) fn categorize_trait(&self, full_path: String, rendered_path: String) -> TraitImpl {
         // Check by explicit crate prefix (like std::fmt::Display)
         let crate_prefix = full_path.split("::").next().unwrap_or("");
         // Check if it's from std crates by prefix
@@ -6,13 +6,26 @@ impl Request {
         &self,
         item: DocRef<'_, Item>,
         type_alias: DocRef<'_, TypeAlias>,
-        _context: &FormatContext,
+        context: &FormatContext,
     ) -> String {
-        let type_str = self.format_type(&type_alias.type_);
-        format!(
-            "\n```rust\ntype {} = {type_str};\n```\n",
-            item.name().unwrap_or("<unnamed>")
-        )
+        let mut result = format!(
+            "\n```rust\n{}\n```\n",
+            self.type_alias_signature(item.name().unwrap_or("<unnamed>"), &type_alias)
+        );
+
+        // the API of the aliased type, which is what the alias is used for
+        if let Some(target) = self.alias_target(item, context.concrete_type()) {
+            result.write_fmt(format_args!("\nAliased type: {}\n", target.type_()));
+            if let Some(docs) = self.docs_to_show(target.item(), true, context) {
+                result.write_fmt(format_args!("\n{docs}\n"));
+            }
+            let context = context
+                .clone()
+                .with_concrete_type(Some(target.type_().clone()));
+            result.push_str(&self.format_associated_methods(target.item(), &context));
+        }
+
+        result
     }
 
     /// Format a union
@@ -35,13 +48,10 @@ impl Request {
         _context: &FormatContext,
     ) -> String {
         let name = item.name().unwrap_or("<unnamed>");
-        let type_str = self.format_type(type_);
-        let mut result = format!("\n```rust\nconst {name}: {type_str}");
-        if let Some(value) = &const_.value {
-            result.write_fmt(format_args!(" = {value}"));
-        }
-        result.push_str(";\n```\n");
-        result
+        format!(
+            "\n```rust\n{}\n```\n",
+            self.constant_signature(name, type_, const_)
+        )
     }
 
     /// Format a static
@@ -51,12 +61,39 @@ impl Request {
         static_item: &Static,
         _context: &FormatContext,
     ) -> String {
-        let type_str = self.format_type(&static_item.type_);
-        let result = format!(
-            "\n```rust\nstatic {}: {type_str} = {};\n```\n",
-            item.name().unwrap_or("<unnamed>"),
-            &static_item.expr
-        );
-        result
+        let name = item.name().unwrap_or("<unnamed>");
+        format!(
+            "\n```rust\n{}\n```\n",
+            self.static_signature(name, static_item)
+        )
+    }
+
+    /// The declaration of a type alias, e.g. `type Result<T> = Result<T, Error>;`
+    pub(super) fn type_alias_signature(&self, name: &str, type_alias: &TypeAlias) -> String {
+        format!(
+            "type {name}{}{} = {};",
+            self.format_generics(&type_alias.generics),
+            self.format_where_clause(&type_alias.generics.where_predicates),
+            self.format_type(&type_alias.type_)
+        )
+    }
+
+    /// The declaration of a constant, with its value if rustdoc recorded one
+    pub(super) fn constant_signature(&self, name: &str, type_: &Type, const_: &Constant) -> String {
+        let mut signature = format!("const {name}: {}", self.format_type(type_));
+        if let Some(value) = &const_.value {
+            signature.write_fmt(format_args!(" = {value}"));
+        }
+        signature.push(';');
+        signature
+    }
+
+    /// The declaration of a static with its initializer
+    pub(super) fn static_signature(&self, name: &str, static_item: &Static) -> String {
+        format!(
+            "static {name}: {} = {};",
+            self.format_type(&static_item.type_),
+            static_item.expr
+        )
     }
 }
@@ -0,0 +1,466 @@
+use rustdoc_types::ItemKind;
+use serde::Serialize;
+
+use super::impls::TraitCategory;
+use super::*;
+
+/// Structured description of a single item, the JSON counterpart of [`Request::format_item`]
+#[derive(Debug, Serialize)]
+pub(crate) struct ItemJson<'a> {
+    name: Option<&'a str>,
+    kind: ItemKind,
+    /// Canonical path where the item is defined, if rustdoc recorded one
+    path: Option<String>,
+    /// Resource URI that resolves to this item
+    uri: Option<&'a str>,
+    visibility: &'a Visibility,
+    /// Declaration header (without body) or full signature for functions, constants and aliases
+    signature: Option<String>,
+    /// What the type parameters stand for, when the item is shown for concrete generic
+    /// arguments, like `T = u8`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    type_arguments: Vec<String>,
+    docs: Option<String>,
+    fields: Vec<FieldJson<'a>>,
+    variants: Vec<VariantJson<'a>>,
+    methods: Vec<MemberJson<'a>>,
+    /// Methods of the types this one derefs to, following `Deref` impls in turn
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    deref_methods: Vec<DerefMethodsJson<'a>>,
+    trait_impls: Vec<TraitImplJson>,
+    /// For a type alias, the type it stands for, whose methods and trait impls are listed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aliased: Option<AliasedJson>,
+    /// Impl blocks left out of `methods` and `trait_impls` because the concrete generic
+    /// arguments do not meet their bounds
+    #[serde(skip_serializing_if = "is_zero")]
+    omitted_impls: usize,
+    /// Children of a module, flattened to paths relative to the module
+    items: Vec<ListingJson>,
+    span: Option<&'a Span>,
+    /// Number of listing entries left off this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    omitted: Option<usize>,
+    /// Pass back as `cursor` to fetch the next page of `items`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct FieldJson<'a> {
+    /// Field name, or its position for tuple fields
+    name: String,
+    #[serde(rename = "type")]
+    type_: String,
+    visibility: &'a Visibility,
+    docs: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct VariantJson<'a> {
+    name: Option<&'a str>,
+    fields: Vec<FieldJson<'a>>,
+    docs: Option<String>,
+}
+
+/// An associated item (method, constant or type) of a type or trait
+#[derive(Debug, Serialize)]
+pub(crate) struct MemberJson<'a> {
+    name: Option<&'a str>,
+    kind: ItemKind,
+    visibility: &'a Visibility,
+    signature: String,
+    docs: Option<String>,
+    span: Option<&'a Span>,
+}
+
+/// The methods reached through one `Deref` impl
+#[derive(Debug, Serialize)]
+pub(crate) struct DerefMethodsJson<'a> {
+    /// The `Target` type, e.g. `str` for `String`
+    target: String,
+    methods: Vec<MemberJson<'a>>,
+}
+
+/// The type a type alias stands for
+#[derive(Debug, Serialize)]
+pub(crate) struct AliasedJson {
+    /// The aliased type with the alias's type parameters filled in, e.g.
+    /// `core::result::Result<T, std::io::error::Error>`
+    #[serde(rename = "type")]
+    type_: String,
+    /// Canonical path of the aliased type, if rustdoc recorded one
+    path: Option<String>,
+    /// First line of the aliased type's docs
+    summary: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct TraitImplJson {
+    /// The trait as written in the impl, including generic arguments
+    #[serde(rename = "trait")]
+    trait_: String,
+    /// Full path of the trait, if known
+    path: Option<String>,
+    category: TraitCategory,
+    /// Whether the type explicitly does not implement the trait, as in `impl !Send for Foo`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    negative: bool,
+    /// The impl's header with generics and where clause, when impl details are requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    header: Option<String>,
+    /// Associated types and constants the impl defines, when impl details are requested
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bindings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ListingJson {
+    path: String,
+    kind: ItemKind,
+    summary: Option<String>,
+}
+
+impl Request {
+    /// Build the JSON description of an item, following the same traversal as `format_item`
+    pub(crate) fn item_json<'a>(
+        &'a self,
+        item: DocRef<'a, Item>,
+        context: &'a FormatContext,
+    ) -> ItemJson<'a> {
+        let mut json = ItemJson {
+            name: item.name(),
+            kind: item.kind(),
+            path: item.path().map(|path| path.to_string()),
+            uri: context.uri(),
+            visibility: &item.item().visibility,
+            signature: self.signature(item),
+            type_arguments: context
+                .concrete_type()
+                .map(|concrete_type| self.type_arguments(item.item(), concrete_type))
+                .unwrap_or_default(),
+            docs: self.docs_to_show(item, false, context),
+            fields: vec![],
+            variants: vec![],
+            methods: vec![],
+            deref_methods: vec![],
+            trait_impls: vec![],
+            aliased: None,
+            omitted_impls: 0,
+            items: vec![],
+            span: item.item().span.as_ref(),
+            omitted: None,
+            next_cursor: None,
+        };
+
+        match item.inner() {
+            ItemEnum::Module(_) => {
+                let mut collected = Vec::new();
+                Self::collect_flat_items(&mut collected, None, item, context);
+                collected.sort_by(|a, b| a.path.cmp(&b.path));
+
+                let page = context.page();
+                let total = collected.len();
+                let shown = page.select(std::iter::repeat_n(0, total));
+                if shown.end < total {
+                    json.omitted = Some(total - shown.end);
+                    json.next_cursor = Some(page.cursor_at(shown.end));
+                }
+
+                json.items = collected
+                    .drain(shown)
+                    .map(|flat_item| ListingJson {
+                        kind: flat_item.item.kind(),
+                        summary: self.docs_to_show(flat_item.item, true, context),
+                        path: flat_item.path,
+                    })
+                    .collect();
+            }
+
+            ItemEnum::Struct(struct_data) => {
+                json.fields = match &struct_data.kind {
+                    StructKind::Unit => vec![],
+                    StructKind::Tuple(fields) => self.tuple_fields_json(item, fields, context),
+                    StructKind::Plain { fields, .. } => item
+                        .id_iter(fields)
+                        .filter_map(|field| self.field_json(field, None, context))
+                        .collect(),
+                };
+                self.add_associated_json(&mut json, item, context);
+            }
+
+            ItemEnum::Enum(enum_data) => {
+                json.variants = item
+                    .id_iter(&enum_data.variants)
+                    .filter_map(|variant| {
+                        let ItemEnum::Variant(variant_data) = variant.inner() else {
+                            return None;
+                        };
+                        let fields = match &variant_data.kind {
+                            VariantKind::Plain => vec![],
+                            VariantKind::Tuple(fields) => {
+                                self.tuple_fields_json(variant, fields, context)
+                            }
+                            VariantKind::Struct { fields, .. } => variant
+                                .id_iter(fields)
+                                .filter_map(|field| self.field_json(field, None, context))
+                                .collect(),
+                        };
+                        Some(VariantJson {
+                            name: variant.name(),
+                            fields,
+                            docs: self.docs_to_show(variant, false, context),
+                        })
+                    })
+                    .collect();
+                self.add_associated_json(&mut json, item, context);
+            }
+
+            ItemEnum::Primitive(_) => self.add_associated_json(&mut json, item, context),
+
+            ItemEnum::TypeAlias(_) => {
+                if let Some(target) = self.alias_target(item, context.concrete_type()) {
+                    json.aliased = Some(AliasedJson {
+                        type_: target.type_().to_string(),
+                        path: target.item().path().map(|path| path.to_string()),
+                        summary: self.docs_to_show(target.item(), true, context),
+                    });
+                    let context = context
+                        .clone()
+                        .with_concrete_type(Some(target.type_().clone()));
+                    self.add_associated_json(&mut json, target.item(), &context);
+                }
+            }
+
+            ItemEnum::Impl(_) => {
+                json.methods = item
+                    .impl_items()
+                    .into_iter()
+                    .map(|impl_item| self.member_json(impl_item, context))
+                    .collect();
+            }
+
+            ItemEnum::Variant(variant_data) => {
+                json.fields = match &variant_data.kind {
+                    VariantKind::Plain => vec![],
+                    VariantKind::Tuple(fields) => self.tuple_fields_json(item, fields, context),
+                    VariantKind::Struct { fields, .. } => item
+                        .id_iter(fields)
+                        .filter_map(|field| self.field_json(field, None, context))
+                        .collect(),
+                };
+            }
+
+            ItemEnum::Trait(trait_data) => {
+                json.methods = item
+                    .id_iter(&trait_data.items)
+                    .map(|trait_item| self.member_json(trait_item, context))
+                    .collect();
+            }
+
+            _ => {}
+        }
+
+        json
+    }
+
+    fn add_associated_json<'a>(
+        &'a self,
+        json: &mut ItemJson<'a>,
+        item: DocRef<'a, Item>,
+        context: &FormatContext,
+    ) {
+        let associated = self.associated_items(item, context);
+        json.omitted_impls = associated.omitted_impls;
+        let method_context = context.clone().with_substitutions(associated.substitutions);
+        json.methods = associated
+            .methods
+            .into_iter()
+            .map(|method| self.member_json(method, &method_context))
+            .collect();
+
+        json.deref_methods = self
+            .deref_targets(item)
+            .into_iter()
+            .map(|target| DerefMethodsJson {
+                target: self.format_type(target.target()),
+                methods: target
+                    .methods()
+                    .map(|method| self.member_json(method, context))
+                    .collect(),
+            })
+            .filter(|deref| !deref.methods.is_empty())
+            .collect();
+
+        json.trait_impls = associated
+            .trait_impls
+            .into_iter()
+            .filter_map(|impl_block| {
+                let ItemEnum::Impl(impl_item) = impl_block.inner() else {
+                    return None;
+                };
+                let trait_path = impl_item.trait_.as_ref()?;
+                let path = impl_block
+                    .crate_docs()
+                    .path(&trait_path.id)
+                    .map(|path| path.to_string());
+                let trait_ = self.format_path(trait_path);
+                let category = self
+                    .categorize_impl(impl_block, impl_item, trait_path)
+                    .category;
+                let (header, bindings) = if context.impl_details() {
+                    (
+                        Some(self.format_impl_header(impl_item)),
+                        self.impl_bindings(impl_block, impl_item),
+                    )
+                } else {
+                    (None, vec![])
+                };
+                Some(TraitImplJson {
+                    trait_,
+                    path,
+                    category,
+                    negative: impl_item.is_negative,
+                    header,
+                    bindings,
+                })
+            })
+            .collect();
+        json.trait_impls.sort_by(|a, b| a.trait_.cmp(&b.trait_));
+    }
+
+    fn field_json<'a>(
+        &self,
+        field: DocRef<'a, Item>,
+        position: Option<usize>,
+        context: &FormatContext,
+    ) -> Option<FieldJson<'a>> {
+        let ItemEnum::StructField(field_type) = field.inner() else {
+            return None;
+        };
+
+        Some(FieldJson {
+            name: match position {
+                Some(position) => position.to_string(),
+                None => field.name().unwrap_or("<unnamed>").to_string(),
+            },
+            type_: self.format_type(field_type),
+            visibility: &field.item().visibility,
+            docs: self.docs_to_show(field, false, context),
+        })
+    }
+
+    fn tuple_fields_json<'a>(
+        &self,
+        item: DocRef<'a, Item>,
+        fields: &[Option<Id>],
+        context: &FormatContext,
+    ) -> Vec<FieldJson<'a>> {
+        fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, id)| {
+                let field = item.get(id.as_ref()?)?;
+                self.field_json(field, Some(i), context)
+            })
+            .collect()
+    }
+
+    fn member_json<'a>(&self, item: DocRef<'a, Item>, context: &FormatContext) -> MemberJson<'a> {
+        MemberJson {
+            name: item.name(),
+            kind: item.kind(),
+            visibility: &item.item().visibility,
+            signature: match item.inner() {
+                ItemEnum::Function(function) => {
+                    let name = item.name().unwrap_or("<unnamed>");
+                    self.method_signature(item, name, function, context)
+                }
+                _ => self.signature(item).unwrap_or_default(),
+            },
+            docs: self.docs_to_show(item, true, context),
+            span: item.item().span.as_ref(),
+        }
+    }
+
+    /// Render the declaration of an item without its body
+    pub(super) fn signature(&self, item: DocRef<'_, Item>) -> Option<String> {
+        let name = item.name().unwrap_or("<unnamed>");
+        let header = |keyword: &str, generics: &Generics| {
+            format!(
+                "{keyword} {name}{}{}",
+                self.format_generics(generics),
+                self.format_where_clause(&generics.where_predicates)
+            )
+        };
+
+        Some(match item.inner() {
+            ItemEnum::Function(function) => self.format_function_signature(name, function),
+            ItemEnum::Struct(struct_data) => header("struct", &struct_data.generics),
+            ItemEnum::Enum(enum_data) => header("enum", &enum_data.generics),
+            ItemEnum::Union(union_data) => header("union", &union_data.generics),
+            ItemEnum::Trait(trait_data) => header("trait", &trait_data.generics),
+            ItemEnum::TypeAlias(type_alias) => self.type_alias_signature(name, type_alias),
+            ItemEnum::Constant { type_, const_ } => self.constant_signature(name, type_, const_),
+            ItemEnum::Static(static_item) => self.static_signature(name, static_item),
+            ItemEnum::AssocConst { type_, value } => {
+                let mut signature = format!("const {name}: {}", self.format_type(type_));
+                if let Some(value) = value {
+                    signature.write_fmt(format_args!(" = {value}"));
+                }
+                signature
+            }
+            ItemEnum::AssocType { bounds, type_, .. } => {
+                let mut signature = format!("type {name}");
+                if !bounds.is_empty() {
+                    signature.write_fmt(format_args!(": {}", self.format_generic_bounds(bounds)));
+                }
+                if let Some(type_) = type_ {
+                    signature.write_fmt(format_args!(" = {}", self.format_type(type_)));
+                }
+                signature
+            }
+            ItemEnum::StructField(type_) => format!("{name}: {}", self.format_type(type_)),
+            ItemEnum::Variant(variant) => {
+                let fields = |fields: &[Id], named: bool| {
+                    let fields = item
+                        .id_iter(fields)
+                        .filter_map(|field| {
+                            let ItemEnum::StructField(type_) = field.inner() else {
+                                return None;
+                            };
+                            let type_ = self.format_type(type_);
+                            Some(match field.name() {
+                                Some(name) if named => format!("{name}: {type_}"),
+                                _ => type_,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    fields.join(", ")
+                };
+                let mut signature = name.to_string();
+                match &variant.kind {
+                    VariantKind::Plain => {}
+                    VariantKind::Tuple(ids) => {
+                        let ids = ids.iter().flatten().copied().collect::<Vec<_>>();
+                        signature.write_fmt(format_args!("({})", fields(&ids, false)));
+                    }
+                    VariantKind::Struct { fields: ids, .. } => {
+                        signature.write_fmt(format_args!(" {{ {} }}", fields(ids, true)));
+                    }
+                }
+                if let Some(discriminant) = &variant.discriminant {
+                    signature.write_fmt(format_args!(" = {}", discriminant.expr));
+                }
+                signature
+            }
+            ItemEnum::Impl(impl_item) => self.format_impl_header(impl_item),
+            ItemEnum::Macro(macro_def) => macro_def.clone(),
+            _ => return None,
+        })
+    }
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
@@ -4,8 +4,9 @@ use crate::traits::WriteFmt;
 use crate::verbosity::Verbosity;
 use rustdoc_types::{
     Abi, Constant, Enum, Function, FunctionPointer, GenericArg, GenericArgs, GenericBound,
-    GenericParamDef, GenericParamDefKind, Generics, Id, Item, ItemEnum, Path, Span, Static, Struct,
-    StructKind, Term, Trait, Type, TypeAlias, Union, VariantKind, Visibility, WherePredicate,
+    GenericParamDef, GenericParamDefKind, Generics, Id, Impl, Item, ItemEnum, Path, Span, Static,
+    Struct, StructKind, Term, Trait, Type, TypeAlias, Union, VariantKind, Visibility,
+    WherePredicate,
 };
 use std::{collections::HashMap, fs};
 
@@ -14,6 +15,7 @@ mod r#enum;
 mod functions;
 mod impls;
 mod items;
+mod json;
 mod r#module;
 mod source;
 mod r#struct;
@@ -34,6 +36,21 @@ impl Request {
             result.write_fmt(format_args!("Defined at: {path}\n"));
         }
 
+        if let Some(uri) = context.uri() {
+            result.write_fmt(format_args!("URI: {uri}\n"));
+        }
+
+        if let Some(concrete_type) = context.concrete_type() {
+            let type_arguments = self.type_arguments(item.item(), concrete_type);
+            result.write_fmt(format_args!("Shown for: {concrete_type}\n"));
+            if !type_arguments.is_empty() {
+                result.write_fmt(format_args!(
+                    "Type arguments: {}\n",
+                    type_arguments.join(", ")
+                ));
+            }
+        }
+
         // Add documentation if available
         if let Some(docs) = self.docs_to_show(item, false, context) {
             result.write_fmt(format_args!("\n{docs}\n\n"));
@@ -63,10 +80,19 @@ impl Request {
                 self.format_constant(item, type_, const_, context)
             }
             ItemEnum::Static(static_data) => self.format_static(item, static_data, context),
+            ItemEnum::Primitive(_) => self.format_associated_methods(item, context),
+            ItemEnum::Impl(impl_item) => self.format_impl(item, impl_item, context),
 
             ItemEnum::Macro(macro_def) => {
                 format!("Macro definition:\n\n```rust\n{macro_def}\n```")
             }
+            ItemEnum::StructField(_)
+            | ItemEnum::Variant(_)
+            | ItemEnum::AssocConst { .. }
+            | ItemEnum::AssocType { .. } => self
+                .signature(item)
+                .map(|signature| format!("\n```rust\n{signature}\n```\n"))
+                .unwrap_or_default(),
             _ => {
                 // For any other item, just print its name and kind
                 format!(
@@ -14,18 +14,23 @@ const GROUP_ORDER: &[(ItemKind, &str)] = &[
     (ItemKind::Constant, "Constants"),
     (ItemKind::Static, "Statics"),
     (ItemKind::Macro, "Macros"),
+    (ItemKind::ProcAttribute, "Attribute Macros"),
+    (ItemKind::ProcDerive, "Derive Macros"),
     (ItemKind::Variant, "Variants"),
+    (ItemKind::AssocConst, "Associated Constants"),
+    (ItemKind::AssocType, "Associated Types"),
+    (ItemKind::Primitive, "Primitives"),
 ];
 
 #[derive(Debug)]
-struct FlatItem<'a> {
-    path: String,
-    item: DocRef<'a, Item>,
+pub(super) struct FlatItem<'a> {
+    pub(super) path: String,
+    pub(super) item: DocRef<'a, Item>,
 }
 
 impl Request {
     /// Collect all items in a module hierarchy as flat qualified paths
-    fn collect_flat_items<'a>(
+    pub(super) fn collect_flat_items<'a>(
         collected: &mut Vec<FlatItem<'a>>,
         path: Option<String>,
         item: DocRef<'a, Item>,
@@ -52,12 +57,11 @@ impl Request {
         }
     }
 
-    /// Format collected flat items with grouping by type
-    fn format_grouped_flat_items(&self, items: &[FlatItem], context: &FormatContext) -> String {
-        if items.is_empty() {
-            return "\nNo items match the current filters.\n".to_string();
-        }
-
+    /// Order flat items for display: grouped by kind in `GROUP_ORDER`, then by path
+    pub(super) fn group_flat_items<'a, 'b>(
+        items: &'b [FlatItem<'a>],
+        context: &FormatContext,
+    ) -> Vec<(String, &'b FlatItem<'a>)> {
         // Group items by filter type
         let mut groups: HashMap<ItemKind, Vec<&FlatItem>> = HashMap::new();
         for flat_item in items {
@@ -65,31 +69,86 @@ impl Request {
             groups.entry(kind).or_default().push(flat_item);
         }
 
-        let mut result = String::new();
+        let mut ordered = Vec::with_capacity(items.len());
 
         for (kind, group_name) in GROUP_ORDER {
             if context.filter_match_kind(*kind)
                 && let Some(mut group_items) = groups.remove(kind)
-                && !group_items.is_empty()
             {
-                result.write_fmt(format_args!("\n{group_name}:\n"));
-
                 group_items.sort_by_key(|a| &a.path);
-
-                for flat_item in group_items {
-                    result.push_str(&self.format_flat_item_line(flat_item, context));
-                }
+                ordered.extend(
+                    group_items
+                        .into_iter()
+                        .map(|flat_item| (group_name.to_string(), flat_item)),
+                );
             }
         }
 
-        for (kind, mut group_items) in groups {
-            result.write_fmt(format_args!("\n{kind:?}:\n"));
+        // Remaining kinds in a stable order, so that pages line up across calls
+        let mut remaining = groups
+            .into_iter()
+            .map(|(kind, items)| (format!("{kind:?}"), items))
+            .collect::<Vec<_>>();
+        remaining.sort_by(|a, b| a.0.cmp(&b.0));
 
+        for (group_name, mut group_items) in remaining {
             group_items.sort_by_key(|a| &a.path);
+            ordered.extend(
+                group_items
+                    .into_iter()
+                    .map(|flat_item| (group_name.clone(), flat_item)),
+            );
+        }
+
+        ordered
+    }
 
-            for flat_item in group_items {
-                result.push_str(&self.format_flat_item_line(flat_item, context));
+    /// Format collected flat items with grouping by type, limited to the current page
+    fn format_grouped_flat_items(&self, items: &[FlatItem], context: &FormatContext) -> String {
+        if items.is_empty() {
+            return "\nNo items match the current filters.\n".to_string();
+        }
+
+        let page = context.page();
+        let ordered = Self::group_flat_items(items, context);
+        let lines = ordered
+            .iter()
+            .map(|(group_name, flat_item)| {
+                (
+                    group_name.as_str(),
+                    self.format_flat_item_line(flat_item, context),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let shown = page.select(lines.iter().map(|(_, line)| line.len()));
+
+        let mut result = String::new();
+        let mut current_group = None;
+
+        for (group_name, line) in &lines[shown.clone()] {
+            if current_group != Some(group_name) {
+                result.write_fmt(format_args!("\n{group_name}:\n"));
+                current_group = Some(group_name);
             }
+            result.push_str(line);
+        }
+
+        let mut omitted: Vec<(&str, usize)> = vec![];
+        for (group_name, _) in &lines[shown.end..] {
+            match omitted.last_mut() {
+                Some((name, count)) if name == group_name => *count += 1,
+                _ => omitted.push((group_name, 1)),
+            }
+        }
+        let breakdown = omitted
+            .iter()
+            .map(|(group_name, count)| format!("{group_name}: {count}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if let Some(note) = page.omission_note(&shown, lines.len(), "items", Some(breakdown)) {
+            result.push_str(&note);
         }
 
         result
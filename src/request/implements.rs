@@ -0,0 +1,928 @@
+//! Answering whether a type implements a trait from the impls rustdoc recorded for it
+
+use super::Request;
+use super::qualified_path::last_segment;
+use super::substitution::{type_generics, with_default_args};
+use crate::doc_ref::DocRef;
+use crate::rustdoc::eq_ignoring_dash_underscore;
+use anyhow::{Result, bail};
+use fieldwork::Fieldwork;
+use rustdoc_types::{
+    GenericArg, GenericArgs, GenericBound, GenericParamDefKind, Impl, Item, ItemEnum, Path,
+    TraitBoundModifier, Type, WherePredicate,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+/// How many bounds deep to follow before giving up, e.g. on recursive types
+const MAX_DEPTH: usize = 8;
+
+/// What an impl's type parameters stand for, by name
+pub(crate) type Bindings = HashMap<String, TypeExpr>;
+
+/// A type as written in a query, e.g. `Vec<Arc<Mutex<T>>>` or `&[u8]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TypeExpr {
+    Path {
+        path: String,
+        args: Vec<TypeExpr>,
+    },
+    Reference {
+        is_mutable: bool,
+        type_: Box<TypeExpr>,
+    },
+    Slice(Box<TypeExpr>),
+    Array(Box<TypeExpr>, String),
+    Tuple(Vec<TypeExpr>),
+}
+
+impl TypeExpr {
+    /// Parse a type, ignoring lifetimes
+    pub(crate) fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser { text, position: 0 };
+        let type_ = parser.type_()?;
+        parser.skip_whitespace();
+        if !parser.rest().is_empty() {
+            bail!("unexpected `{}` in type `{text}`", parser.rest());
+        }
+        Ok(type_)
+    }
+}
+
+impl Display for TypeExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut Formatter<'_>, types: &[TypeExpr]| {
+            for (i, type_) in types.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{type_}")?;
+            }
+            Ok(())
+        };
+
+        match self {
+            Self::Path { path, args } => {
+                f.write_str(path)?;
+                if !args.is_empty() {
+                    f.write_str("<")?;
+                    list(f, args)?;
+                    f.write_str(">")?;
+                }
+                Ok(())
+            }
+            Self::Reference { is_mutable, type_ } => {
+                let mutability = if *is_mutable { "mut " } else { "" };
+                write!(f, "&{mutability}{type_}")
+            }
+            Self::Slice(type_) => write!(f, "[{type_}]"),
+            Self::Array(type_, len) => write!(f, "[{type_}; {len}]"),
+            Self::Tuple(types) => {
+                f.write_str("(")?;
+                list(f, types)?;
+                if types.len() == 1 {
+                    f.write_str(",")?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.position = self.text.len() - self.rest().trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.rest().starts_with(token);
+        if found {
+            self.position += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        if !self.eat(token) {
+            bail!(
+                "expected `{token}` at `{}` in type `{}`",
+                self.rest(),
+                self.text
+            );
+        }
+        Ok(())
+    }
+
+    fn identifier(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.position += len;
+        (len > 0).then(|| &rest[..len])
+    }
+
+    /// Skip a lifetime like `'a`, if there is one
+    fn lifetime(&mut self) -> bool {
+        let found = self.eat("'");
+        if found {
+            self.identifier();
+        }
+        found
+    }
+
+    fn type_(&mut self) -> Result<TypeExpr> {
+        if self.eat("&") {
+            self.lifetime();
+            let is_mutable = self.rest().trim_start().starts_with("mut ") && self.eat("mut");
+            let type_ = Box::new(self.type_()?);
+            return Ok(TypeExpr::Reference { is_mutable, type_ });
+        }
+
+        if self.eat("[") {
+            let type_ = Box::new(self.type_()?);
+            if self.eat(";") {
+                let len = self.rest().split(']').next().unwrap_or_default();
+                self.position += len.len();
+                self.expect("]")?;
+                return Ok(TypeExpr::Array(type_, len.trim().to_string()));
+            }
+            self.expect("]")?;
+            return Ok(TypeExpr::Slice(type_));
+        }
+
+        if self.eat("(") {
+            let trailing_comma =
+                |parser: &Self| parser.text[..parser.position].trim_end().ends_with(",)");
+            let mut types = self.list(")")?;
+            if types.len() == 1 && !trailing_comma(self) {
+                return Ok(types.remove(0));
+            }
+            return Ok(TypeExpr::Tuple(types));
+        }
+
+        let mut segments = vec![];
+        let mut args = vec![];
+        loop {
+            let Some(segment) = self.identifier() else {
+                bail!("expected a type at `{}` in `{}`", self.rest(), self.text);
+            };
+            segments.push(segment);
+            if self.eat("<") {
+                args = self.list(">")?;
+                break;
+            }
+            if !self.eat("::") {
+                break;
+            }
+            // turbofish, as in `Vec::<u8>`
+            if self.eat("<") {
+                args = self.list(">")?;
+                break;
+            }
+        }
+        Ok(TypeExpr::Path {
+            path: segments.join("::"),
+            args,
+        })
+    }
+
+    /// Types up to a closing `close`, separated by commas, skipping lifetimes
+    fn list(&mut self, close: &str) -> Result<Vec<TypeExpr>> {
+        let mut types = vec![];
+        loop {
+            if self.eat(close) {
+                return Ok(types);
+            }
+            if !self.lifetime() {
+                types.push(self.type_()?);
+            }
+            if !self.eat(",") {
+                self.expect(close)?;
+                return Ok(types);
+            }
+        }
+    }
+}
+
+/// Whether a type implements a trait
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Verdict {
+    Implemented,
+    NotImplemented,
+    /// The docs do not say, e.g. because a type parameter was not given or a crate's docs are
+    /// missing
+    Unknown,
+}
+
+/// How an impl's type compares to a query type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unification {
+    Matches,
+    Differs,
+    /// The docs cannot tell, e.g. for `dyn Trait` or a path whose docs were not found
+    Unclear,
+}
+
+impl Unification {
+    /// Combine the comparisons of a type's parts, stopping at the first part that differs
+    fn all(parts: impl IntoIterator<Item = Self>) -> Self {
+        let mut combined = Self::Matches;
+        for part in parts {
+            match part {
+                Self::Differs => return Self::Differs,
+                Self::Unclear => combined = Self::Unclear,
+                Self::Matches => {}
+            }
+        }
+        combined
+    }
+}
+
+/// Whether a type implements a trait, with the impl that decides it and that impl's bounds
+#[derive(Debug, Serialize, Fieldwork)]
+#[fieldwork(get)]
+pub(crate) struct Explanation {
+    #[serde(rename = "type")]
+    type_: String,
+    #[serde(rename = "trait")]
+    trait_: String,
+    #[field(copy)]
+    verdict: Verdict,
+    /// The impl that applies, or why none does
+    reason: String,
+    /// The impl's bounds, each checked in turn
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bounds: Vec<Explanation>,
+}
+
+impl Explanation {
+    fn new(type_: String, trait_: &str, verdict: Verdict, reason: String) -> Self {
+        Self {
+            type_,
+            trait_: trait_.to_string(),
+            verdict,
+            reason,
+            bounds: vec![],
+        }
+    }
+}
+
+/// A trait as a query or an impl's bound names it, with the type arguments it is given, e.g.
+/// `From<String>`
+#[derive(Debug, Clone)]
+pub(super) struct TraitRef {
+    /// Where the trait is defined, e.g. `core::convert::From`, if its docs were found
+    path: Option<Vec<String>>,
+    name: String,
+    /// Impls are matched against as many arguments as are given
+    args: Vec<TypeExpr>,
+}
+
+impl TraitRef {
+    /// The crate the trait is defined in, as rustdoc names it in paths
+    fn crate_name(&self) -> Option<&str> {
+        self.path.as_ref()?.first().map(String::as_str)
+    }
+
+    /// Whether an impl is of this trait, binding the impl's type parameters that appear in the
+    /// trait's arguments
+    fn matches(
+        &self,
+        request: &Request,
+        impl_block: DocRef<'_, Item>,
+        bindings: &mut Bindings,
+    ) -> Unification {
+        let ItemEnum::Impl(Impl {
+            trait_: Some(trait_),
+            ..
+        }) = impl_block.inner()
+        else {
+            return Unification::Differs;
+        };
+        if last_segment(&trait_.path) != self.name {
+            return Unification::Differs;
+        }
+        if let Some(path) = &self.path
+            && let Some(impl_path) = impl_block.crate_docs().path(&trait_.id)
+            && impl_path.segments() != path.as_slice()
+        {
+            return Unification::Differs;
+        }
+        let trait_item = self
+            .path
+            .as_ref()
+            .and_then(|path| request.resolve_path(&path.join("::"), &mut vec![]));
+        request.unify_args(
+            impl_block,
+            type_args(trait_),
+            trait_item,
+            &self.args,
+            bindings,
+        )
+    }
+}
+
+impl Display for TraitRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let trait_ = TypeExpr::Path {
+            path: self.name.clone(),
+            args: self.args.clone(),
+        };
+        write!(f, "{trait_}")
+    }
+}
+
+impl Request {
+    /// Work out whether a type implements a trait, from the trait's impls for the type, blanket
+    /// impls and auto trait impls, checking each impl's bounds against the type's arguments
+    ///
+    /// Only impls documented in the type's own crate are found, which are all there are when
+    /// the trait is defined in that crate or one it depends on. Otherwise, finding no impl leaves
+    /// the answer unknown.
+    pub(crate) fn explain_implements(&self, type_: &TypeExpr, trait_: &TypeExpr) -> Explanation {
+        match self.trait_ref(trait_) {
+            Some(trait_) => self.check_implements(type_, &trait_, 0),
+            None => Explanation::new(
+                type_.to_string(),
+                &trait_.to_string(),
+                Verdict::Unknown,
+                format!("`{trait_}` is not a trait path"),
+            ),
+        }
+    }
+
+    /// Resolve a trait as a query names it, e.g. `From<String>` or `std::io::Write`
+    ///
+    /// A trait without docs is still matched by name and arguments.
+    pub(super) fn trait_ref(&self, trait_: &TypeExpr) -> Option<TraitRef> {
+        let TypeExpr::Path { path, args } = trait_ else {
+            return None;
+        };
+        let path_segments = self
+            .type_item(trait_)
+            .filter(|item| matches!(item.inner(), ItemEnum::Trait(_)))
+            .and_then(|item| item.path())
+            .map(|path| path.segments().to_vec());
+        Some(TraitRef {
+            path: path_segments,
+            name: last_segment(path).to_string(),
+            args: args.clone(),
+        })
+    }
+
+    fn check_implements(&self, type_: &TypeExpr, trait_: &TraitRef, depth: usize) -> Explanation {
+        let trait_name = trait_.to_string();
+        let unknown = |reason: String| {
+            Explanation::new(type_.to_string(), &trait_name, Verdict::Unknown, reason)
+        };
+        if depth > MAX_DEPTH {
+            return unknown("too many nested bounds to follow".into());
+        }
+        let Some(item) = self.type_item(type_) else {
+            return unknown(format!(
+                "no docs found for `{type_}`; if it is a type parameter, this depends on its bounds"
+            ));
+        };
+
+        // std and core each list the impls on a primitive that they define
+        let searched = self.type_docs(item);
+
+        // impls bounding a defaulted parameter, like `Vec`'s allocator, are checked against
+        // the default
+        let with_defaults = with_default_args(item, type_);
+
+        let mut best: Option<Explanation> = None;
+        for impl_block in searched.iter().flat_map(|item| item.traits()) {
+            let ItemEnum::Impl(impl_item) = impl_block.inner() else {
+                continue;
+            };
+            let Some((mut explanation, _)) =
+                self.bind_impl(impl_block, &with_defaults, Some(trait_), depth)
+            else {
+                continue;
+            };
+            explanation.type_ = type_.to_string();
+            if impl_item.is_negative {
+                return Explanation::new(
+                    type_.to_string(),
+                    &trait_name,
+                    Verdict::NotImplemented,
+                    format!(
+                        "explicitly not implemented: {}",
+                        self.one_line_impl_header(impl_item)
+                    ),
+                );
+            }
+
+            match (explanation.verdict, &best) {
+                (Verdict::Implemented, _) => return explanation,
+                (Verdict::Unknown, Some(b)) if b.verdict == Verdict::NotImplemented => {
+                    best = Some(explanation)
+                }
+                (_, None) => best = Some(explanation),
+                _ => {}
+            }
+        }
+
+        best.unwrap_or_else(|| {
+            let crate_names = searched
+                .iter()
+                .map(|item| item.crate_docs().name())
+                .collect::<Vec<_>>();
+            let not_found = format!(
+                "no impl of `{trait_name}` for `{type_}` in the docs of {}",
+                crate_names.join(" or ")
+            );
+            // an impl of a trait from a crate these depend on can only be in these crates, as the
+            // trait's crate cannot name their types
+            let complete = trait_.crate_name().is_some_and(|trait_crate| {
+                searched.iter().any(|item| {
+                    let crate_docs = item.crate_docs();
+                    eq_ignoring_dash_underscore(crate_docs.name(), trait_crate)
+                        || crate_docs
+                            .external_crates
+                            .values()
+                            .any(|external| external.name == trait_crate)
+                })
+            });
+            match trait_.crate_name() {
+                _ if complete => Explanation::new(
+                    type_.to_string(),
+                    &trait_name,
+                    Verdict::NotImplemented,
+                    not_found,
+                ),
+                Some(trait_crate) => unknown(format!(
+                    "{not_found}; impls in {trait_crate}, where `{}` is defined, are not searched",
+                    trait_.name
+                )),
+                None => unknown(format!("{not_found}; no docs found for `{}`", trait_.name)),
+            }
+        })
+    }
+
+    /// Check one impl against a type: whether its self type matches, binding its type
+    /// parameters, and whether the type's arguments meet its bounds
+    ///
+    /// Returns None if the impl is for a different type. Negative impls are not handled here.
+    pub(super) fn check_impl(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        type_: &TypeExpr,
+        depth: usize,
+    ) -> Option<Explanation> {
+        self.bind_impl(impl_block, type_, None, depth)
+            .map(|(explanation, _)| explanation)
+    }
+
+    /// The type parameters an impl binds for a type, if the type meets the impl's bounds or it
+    /// cannot be told whether it does
+    ///
+    /// Works for inherent impls as well as trait impls, e.g. `impl<T: Copy> Cell<T>` applies to
+    /// `Cell<u8>` with `T` bound to `u8`, but not to `Cell<String>`.
+    pub(crate) fn applicable_impl(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        type_: &TypeExpr,
+    ) -> Option<Bindings> {
+        self.bind_impl(impl_block, type_, None, 0)
+            .filter(|(explanation, _)| explanation.verdict != Verdict::NotImplemented)
+            .map(|(_, bindings)| bindings)
+    }
+
+    /// Match an impl against a type and, if given, the trait and trait arguments it must be an
+    /// impl of
+    fn bind_impl(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        type_: &TypeExpr,
+        trait_: Option<&TraitRef>,
+        depth: usize,
+    ) -> Option<(Explanation, Bindings)> {
+        let ItemEnum::Impl(impl_item) = impl_block.inner() else {
+            return None;
+        };
+        let (bindings, certain) = self.match_impl(impl_block, type_, trait_)?;
+
+        let bounds = impl_bounds(impl_item)
+            .into_iter()
+            .map(|(bounded, bound)| {
+                let bound = bound_trait(impl_block, bound, &bindings);
+                match substitute(impl_block, &bounded, &bindings) {
+                    Some(concrete) => self.check_implements(&concrete, &bound, depth + 1),
+                    None => Explanation::new(
+                        self.format_type(&bounded),
+                        &bound.to_string(),
+                        Verdict::Unknown,
+                        "depends on a type parameter the query does not give".into(),
+                    ),
+                }
+            })
+            .collect::<Vec<_>>();
+        let verdict = if bounds.iter().any(|b| b.verdict == Verdict::NotImplemented) {
+            Verdict::NotImplemented
+        } else if !certain || bounds.iter().any(|b| b.verdict == Verdict::Unknown) {
+            Verdict::Unknown
+        } else {
+            Verdict::Implemented
+        };
+        let trait_name = match (trait_, &impl_item.trait_) {
+            (Some(trait_), _) => trait_.to_string(),
+            (None, Some(trait_path)) => last_segment(&trait_path.path).to_string(),
+            (None, None) => String::new(),
+        };
+        let explanation = Explanation {
+            type_: type_.to_string(),
+            trait_: trait_name,
+            verdict,
+            reason: if certain {
+                format!("via {}", self.one_line_impl_header(impl_item))
+            } else {
+                format!(
+                    "via {}, if it applies; the docs cannot tell whether it is for `{type_}`",
+                    self.one_line_impl_header(impl_item)
+                )
+            },
+            bounds,
+        };
+        Some((explanation, bindings))
+    }
+
+    /// Match an impl's self type against a type and, if given, its trait against a trait,
+    /// binding the impl's type parameters, without checking the impl's bounds
+    ///
+    /// Returns None if the impl is for a different type or trait, and otherwise whether the docs
+    /// tell for certain that it is for this one.
+    pub(super) fn match_impl(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        type_: &TypeExpr,
+        trait_: Option<&TraitRef>,
+    ) -> Option<(Bindings, bool)> {
+        let ItemEnum::Impl(impl_item) = impl_block.inner() else {
+            return None;
+        };
+
+        let mut bindings = HashMap::from([("Self".to_string(), type_.clone())]);
+        let self_type = match &impl_item.blanket_impl {
+            Some(Type::Generic(name)) => {
+                bindings.insert(name.clone(), type_.clone());
+                Unification::Matches
+            }
+            _ => self.unify(impl_block, &impl_item.for_, type_, &mut bindings),
+        };
+        let trait_ = match trait_ {
+            Some(trait_) if self_type != Unification::Differs => {
+                trait_.matches(self, impl_block, &mut bindings)
+            }
+            _ => Unification::Matches,
+        };
+        match Unification::all([self_type, trait_]) {
+            Unification::Differs => None,
+            unification => Some((bindings, unification == Unification::Matches)),
+        }
+    }
+
+    /// Match an impl's type against a query type, binding the impl's type parameters
+    ///
+    /// Paths are compared by where they are defined, so an impl for `io::Error` is not one for
+    /// `fmt::Error`. Types rustdoc records that a query cannot be compared to, like `dyn Trait`,
+    /// leave the match unclear.
+    fn unify(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        pattern: &Type,
+        type_: &TypeExpr,
+        bindings: &mut Bindings,
+    ) -> Unification {
+        let same = |same: bool| {
+            if same {
+                Unification::Matches
+            } else {
+                Unification::Differs
+            }
+        };
+        match (pattern, type_) {
+            (Type::Generic(name), _) => match bindings.get(name) {
+                Some(bound) => same(bound == type_),
+                None => {
+                    bindings.insert(name.clone(), type_.clone());
+                    Unification::Matches
+                }
+            },
+            (Type::ResolvedPath(path), TypeExpr::Path { path: name, args }) => {
+                if last_segment(&path.path) != last_segment(name) {
+                    return Unification::Differs;
+                }
+                let item = self.type_item(type_);
+                let defined = match (
+                    impl_block.crate_docs().path(&path.id),
+                    item.and_then(|item| item.path()),
+                ) {
+                    (Some(pattern_path), Some(query_path)) => {
+                        same(pattern_path.segments() == query_path.segments())
+                    }
+                    _ => Unification::Unclear,
+                };
+                if defined == Unification::Differs {
+                    return defined;
+                }
+                let args = match item.map(|item| with_default_args(item, type_)) {
+                    Some(TypeExpr::Path { args, .. }) => args,
+                    _ => args.clone(),
+                };
+                Unification::all([
+                    defined,
+                    self.unify_args(impl_block, type_args(path), item, &args, bindings),
+                ])
+            }
+            (Type::Primitive(primitive), TypeExpr::Path { path, args }) => {
+                same(args.is_empty() && last_segment(path) == primitive)
+            }
+            (
+                Type::BorrowedRef {
+                    is_mutable,
+                    type_: pattern,
+                    ..
+                },
+                TypeExpr::Reference {
+                    is_mutable: query_is_mutable,
+                    type_,
+                },
+            ) => Unification::all([
+                same(is_mutable == query_is_mutable),
+                self.unify(impl_block, pattern, type_, bindings),
+            ]),
+            (Type::Slice(pattern), TypeExpr::Slice(type_))
+            | (Type::Array { type_: pattern, .. }, TypeExpr::Array(type_, _)) => {
+                self.unify(impl_block, pattern, type_, bindings)
+            }
+            (Type::Tuple(patterns), TypeExpr::Tuple(types)) if patterns.len() == types.len() => {
+                Unification::all(
+                    patterns
+                        .iter()
+                        .zip(types)
+                        .map(|(pattern, type_)| self.unify(impl_block, pattern, type_, bindings)),
+                )
+            }
+            // a query cannot write pointers, so these never match
+            (
+                Type::ResolvedPath(_)
+                | Type::Primitive(_)
+                | Type::BorrowedRef { .. }
+                | Type::Slice(_)
+                | Type::Array { .. }
+                | Type::Tuple(_)
+                | Type::RawPointer { .. }
+                | Type::FunctionPointer(_),
+                _,
+            ) => Unification::Differs,
+            // `dyn Trait`, `impl Trait` and `<T as Trait>::Assoc` could stand for the query type
+            _ => Unification::Unclear,
+        }
+    }
+
+    /// Match the arguments an impl gives a type or trait against a query's arguments
+    ///
+    /// rustdoc leaves out arguments the impl does not write, as in `impl PartialEq for Foo`, so
+    /// those are taken from the defaults `item` declares. Arguments the query leaves out match
+    /// anything, but a query giving more arguments than the impl has does not match.
+    fn unify_args(
+        &self,
+        impl_block: DocRef<'_, Item>,
+        written: Vec<&Type>,
+        item: Option<DocRef<'_, Item>>,
+        args: &[TypeExpr],
+        bindings: &mut Bindings,
+    ) -> Unification {
+        let mut patterns = written
+            .into_iter()
+            .map(|pattern| (impl_block, pattern))
+            .collect::<Vec<_>>();
+        let defaults = item
+            .and_then(|item| Some((item, type_generics(item.item())?)))
+            .map(|(item, generics)| {
+                generics
+                    .params
+                    .iter()
+                    .filter_map(move |param| match &param.kind {
+                        GenericParamDefKind::Type { default, .. } => Some((item, default.as_ref())),
+                        _ => None,
+                    })
+                    .skip(patterns.len())
+                    .map_while(|(item, default)| Some((item, default?)))
+                    .collect::<Vec<_>>()
+            });
+        let extra = match defaults {
+            Some(defaults) => {
+                patterns.extend(defaults);
+                Unification::Differs
+            }
+            // without the docs, there is no telling whether the query's extra arguments are
+            // the defaults
+            None => Unification::Unclear,
+        };
+        if patterns.len() < args.len() {
+            return extra;
+        }
+        Unification::all(
+            patterns
+                .into_iter()
+                .zip(args)
+                .map(|(pattern, arg)| self.unify(pattern.0, pattern.1, arg, bindings)),
+        )
+    }
+
+    fn one_line_impl_header(&self, impl_item: &Impl) -> String {
+        self.format_impl_header(impl_item)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The documented type a query type refers to, looking bare names up in the std prelude
+    pub(super) fn type_item(&self, type_: &TypeExpr) -> Option<DocRef<'_, Item>> {
+        let path = match type_ {
+            TypeExpr::Path { path, .. } => path,
+            TypeExpr::Reference { .. } => return self.primitive("reference"),
+            TypeExpr::Slice(_) => return self.primitive("slice"),
+            TypeExpr::Array(..) => return self.primitive("array"),
+            TypeExpr::Tuple(types) if types.is_empty() => return self.primitive("unit"),
+            TypeExpr::Tuple(_) => return self.primitive("tuple"),
+        };
+        self.resolve_path(path, &mut vec![]).or_else(|| {
+            (!path.contains("::"))
+                .then(|| self.resolve_path(&format!("std::prelude::v1::{path}"), &mut vec![]))
+                .flatten()
+        })
+    }
+}
+
+/// The trait an impl's bound requires, with the impl's type parameters in its arguments replaced
+/// by what they are bound to
+///
+/// If an argument uses a parameter the query leaves open, the trait is matched without arguments.
+fn bound_trait(impl_block: DocRef<'_, Item>, bound: &Path, bindings: &Bindings) -> TraitRef {
+    TraitRef {
+        path: impl_block
+            .crate_docs()
+            .path(&bound.id)
+            .map(|path| path.segments().to_vec()),
+        name: last_segment(&bound.path).to_string(),
+        args: type_args(bound)
+            .into_iter()
+            .map(|arg| substitute(impl_block, arg, bindings))
+            .collect::<Option<_>>()
+            .unwrap_or_default(),
+    }
+}
+
+/// The trait bounds an impl places on types, as the bounded type and the trait
+///
+/// `?Sized` and lifetime bounds are left out.
+fn impl_bounds(impl_item: &Impl) -> Vec<(Type, &Path)> {
+    let mut bounds = vec![];
+    for param in &impl_item.generics.params {
+        if let GenericParamDefKind::Type {
+            bounds: param_bounds,
+            ..
+        } = &param.kind
+        {
+            for trait_ in trait_bounds(param_bounds) {
+                bounds.push((Type::Generic(param.name.clone()), trait_));
+            }
+        }
+    }
+    for predicate in &impl_item.generics.where_predicates {
+        if let WherePredicate::BoundPredicate {
+            type_,
+            bounds: predicate_bounds,
+            ..
+        } = predicate
+        {
+            for trait_ in trait_bounds(predicate_bounds) {
+                bounds.push((type_.clone(), trait_));
+            }
+        }
+    }
+    bounds
+}
+
+/// The traits in a list of bounds, leaving out `?Sized` and lifetimes
+fn trait_bounds(bounds: &[GenericBound]) -> impl Iterator<Item = &Path> {
+    bounds.iter().filter_map(|bound| match bound {
+        GenericBound::TraitBound {
+            trait_,
+            modifier: TraitBoundModifier::None,
+            ..
+        } => Some(trait_),
+        _ => None,
+    })
+}
+
+/// The type arguments of a path, e.g. `T` in `Vec<T>`
+fn type_args(path: &Path) -> Vec<&Type> {
+    match path.args.as_deref() {
+        Some(GenericArgs::AngleBracketed { args, .. }) => args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArg::Type(type_) => Some(type_),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Replace an impl's type parameters in a type with what they are bound to, giving a query type
+///
+/// Paths are made absolute where rustdoc knows them, so that they resolve from anywhere.
+/// Returns None if the type uses a parameter that is not bound, or cannot be expressed.
+pub(super) fn substitute(
+    impl_block: DocRef<'_, Item>,
+    type_: &Type,
+    bindings: &Bindings,
+) -> Option<TypeExpr> {
+    Some(match type_ {
+        Type::Generic(name) => bindings.get(name)?.clone(),
+        Type::ResolvedPath(path) => TypeExpr::Path {
+            path: impl_block
+                .crate_docs()
+                .path(&path.id)
+                .map(|path| path.to_string())
+                .unwrap_or_else(|| path.path.clone()),
+            args: type_args(path)
+                .into_iter()
+                .map(|arg| substitute(impl_block, arg, bindings))
+                .collect::<Option<_>>()?,
+        },
+        Type::Primitive(name) => TypeExpr::Path {
+            path: name.clone(),
+            args: vec![],
+        },
+        Type::BorrowedRef {
+            is_mutable, type_, ..
+        } => TypeExpr::Reference {
+            is_mutable: *is_mutable,
+            type_: Box::new(substitute(impl_block, type_, bindings)?),
+        },
+        Type::Slice(type_) => TypeExpr::Slice(Box::new(substitute(impl_block, type_, bindings)?)),
+        Type::Array { type_, len } => TypeExpr::Array(
+            Box::new(substitute(impl_block, type_, bindings)?),
+            len.clone(),
+        ),
+        Type::Tuple(types) => TypeExpr::Tuple(
+            types
+                .iter()
+                .map(|type_| substitute(impl_block, type_, bindings))
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_types() {
+        for text in [
+            "Vec<u8>",
+            "std::sync::Arc<std::sync::Mutex<T>>",
+            "&mut [u8]",
+            "[u8; 4]",
+            "(u8, String)",
+            "(u8,)",
+            "()",
+            "HashMap<String, Vec<&str>>",
+        ] {
+            assert_eq!(TypeExpr::parse(text).unwrap().to_string(), text);
+        }
+
+        assert_eq!(
+            TypeExpr::parse("&'a Cow<'static, str>")
+                .unwrap()
+                .to_string(),
+            "&Cow<str>"
+        );
+        assert_eq!(TypeExpr::parse("Vec::<u8>").unwrap().to_string(), "Vec<u8>");
+        assert_eq!(TypeExpr::parse("(u8)").unwrap().to_string(), "u8");
+        assert!(TypeExpr::parse("Vec<u8").is_err());
+        assert!(TypeExpr::parse("Vec<u8>>").is_err());
+    }
+}
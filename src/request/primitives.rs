@@ -0,0 +1,69 @@
+use super::Request;
+use crate::doc_ref::DocRef;
+use rustdoc_types::{Item, ItemEnum};
+
+/// Names rustdoc documents primitive types under, which can start a path like a crate name
+pub(super) const PRIMITIVES: &[&str] = &[
+    "array",
+    "bool",
+    "char",
+    "f16",
+    "f32",
+    "f64",
+    "f128",
+    "fn",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "never",
+    "pointer",
+    "reference",
+    "slice",
+    "str",
+    "tuple",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "unit",
+    "usize",
+];
+
+/// Crates that document the primitives, in the order they are looked in
+const PRIMITIVE_CRATES: &[&str] = &["std", "core"];
+
+impl Request {
+    /// Find the documentation of a primitive type by its bare name, e.g. `str` or `slice`
+    ///
+    /// Primitives are documented in std and core, which also hold their inherent impls.
+    pub(super) fn primitive<'a>(&'a self, name: &str) -> Option<DocRef<'a, Item>> {
+        self.primitive_docs(name).next()
+    }
+
+    /// Every documentation of a type, each listing the impls its crate defines: several for a
+    /// primitive, and the item itself for other types
+    pub(super) fn type_docs<'a>(&'a self, item: DocRef<'a, Item>) -> Vec<DocRef<'a, Item>> {
+        match item.inner() {
+            ItemEnum::Primitive(primitive) => self.primitive_docs(&primitive.name).collect(),
+            _ => vec![item],
+        }
+    }
+
+    /// Every crate's documentation of a primitive type, as each crate lists the impls it
+    /// defines: std its additions, like float math, and core the rest, including most trait
+    /// impls
+    pub(super) fn primitive_docs<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl Iterator<Item = DocRef<'a, Item>> + use<'a> {
+        let name = PRIMITIVES.iter().find(|primitive| **primitive == name);
+        PRIMITIVE_CRATES
+            .iter()
+            .filter(move |_| name.is_some())
+            .filter_map(move |crate_name| self.load(crate_name)?.primitive(self, name?))
+    }
+}
@@ -0,0 +1,212 @@
+use super::implements::TypeExpr;
+use super::substitution::with_default_args;
+use super::{Request, Suggestion};
+use crate::doc_ref::DocRef;
+use crate::string_utils::case_aware_jaro_winkler;
+use rustc_hash::FxHashSet;
+use rustdoc_types::{Item, ItemEnum};
+
+impl Request {
+    /// Resolve a fully qualified path such as `<std::vec::Vec<T> as IntoIterator>::IntoIter`
+    ///
+    /// The item named after the qualified self type is looked for among the items of the
+    /// type's impls of that trait, including methods the trait provides, and then among the
+    /// trait's own items. `<T>::item` is the same as `T::item`. Generic arguments are ignored.
+    pub(super) fn resolve_qualified_path<'a>(
+        &'a self,
+        path: &str,
+        suggestions: &mut Vec<Suggestion<'a>>,
+    ) -> Option<DocRef<'a, Item>> {
+        let (qualified, rest) = split_qualified_self(path)?;
+        let rest = rest.strip_prefix("::")?;
+        let Some((self_type, trait_path)) = split_as(qualified) else {
+            return self.resolve_path(
+                &format!("{}::{rest}", strip_generics(qualified)),
+                suggestions,
+            );
+        };
+        let self_type = strip_generics(self_type);
+        let trait_path = strip_generics(trait_path);
+        let trait_name = last_segment(&trait_path);
+
+        let self_item = self.resolve_path(&self_type, suggestions)?;
+        let mut candidates = trait_impls_named(self_item, trait_name)
+            .flat_map(|impl_block| impl_block.impl_items())
+            .collect::<Vec<_>>();
+        if let Some(trait_item) = self.resolve_path(&trait_path, &mut vec![]) {
+            candidates.extend(trait_item.members());
+        }
+
+        let (member_name, remaining) = rest.split_once("::").unwrap_or((rest, ""));
+        let Some(member) = candidates
+            .iter()
+            .find(|candidate| candidate.name() == Some(member_name))
+        else {
+            suggestions.extend(candidates.into_iter().filter_map(|candidate| {
+                let name = candidate.name()?;
+                Some(Suggestion {
+                    path: format!("<{self_type} as {trait_path}>::{name}"),
+                    item: Some(candidate),
+                    score: case_aware_jaro_winkler(member_name, name),
+                })
+            }));
+            return None;
+        };
+
+        if remaining.is_empty() {
+            Some(*member)
+        } else {
+            self.find_children_recursive(*member, path, path.len() - remaining.len(), suggestions)
+        }
+    }
+
+    /// Resolve `Type as Trait` to the impl block of that trait for that type
+    ///
+    /// The trait is matched by where it is defined and by any generic arguments given, so
+    /// `crate::Foo as Iterator` and `crate::Foo as std::iter::Iterator` are the same, and
+    /// `u32 as From<u8>` picks one of `u32`'s `From` impls. If the arguments leave several impls
+    /// matching, they are all suggested.
+    pub(super) fn resolve_impl<'a>(
+        &'a self,
+        self_path: &str,
+        trait_path: &str,
+        suggestions: &mut Vec<Suggestion<'a>>,
+    ) -> Option<DocRef<'a, Item>> {
+        let self_path = self_path.trim();
+        let self_item = self.resolve_path(&strip_generics(self_path), suggestions)?;
+        let trait_ = TypeExpr::parse(trait_path)
+            .ok()
+            .and_then(|trait_| self.trait_ref(&trait_));
+        let self_type = TypeExpr::parse(self_path).ok();
+        if let (Some(trait_), Some(self_type)) = (&trait_, &self_type) {
+            let self_type = with_default_args(self_item, self_type);
+            let mut matching = self
+                .type_docs(self_item)
+                .into_iter()
+                .flat_map(|item| item.traits())
+                .filter(|impl_block| {
+                    self.match_impl(*impl_block, &self_type, Some(trait_))
+                        .is_some()
+                })
+                .collect::<Vec<_>>();
+            if matching.len() == 1 {
+                return matching.pop();
+            }
+
+            if !matching.is_empty() {
+                suggestions.extend(matching.into_iter().filter_map(|impl_block| {
+                    Some(Suggestion {
+                        path: format!("{self_path} as {}", self.impl_trait(impl_block)?),
+                        item: Some(impl_block),
+                        score: 1.0,
+                    })
+                }));
+                return None;
+            }
+        }
+
+        let trait_name = last_segment(&strip_generics(trait_path)).to_string();
+        // std and core both list blanket impls on primitives
+        let mut seen = FxHashSet::default();
+        let impls = self
+            .type_docs(self_item)
+            .into_iter()
+            .flat_map(|item| item.traits());
+        suggestions.extend(impls.filter_map(|impl_block| {
+            let ItemEnum::Impl(impl_data) = impl_block.inner() else {
+                return None;
+            };
+            let implemented = last_segment(&impl_data.trait_.as_ref()?.path);
+            let path = format!("{self_path} as {}", self.impl_trait(impl_block)?);
+            if !seen.insert(path.clone()) {
+                return None;
+            }
+            Some(Suggestion {
+                path,
+                item: Some(impl_block),
+                score: case_aware_jaro_winkler(&trait_name, implemented),
+            })
+        }));
+        None
+    }
+
+    /// The trait an impl implements, with its arguments, e.g. `From<u8>`
+    fn impl_trait(&self, impl_block: DocRef<'_, Item>) -> Option<String> {
+        let ItemEnum::Impl(impl_data) = impl_block.inner() else {
+            return None;
+        };
+        Some(self.format_path(impl_data.trait_.as_ref()?))
+    }
+}
+
+/// The impls of a trait for an item, matching the trait by the last segment of its path
+pub(super) fn trait_impls_named<'a>(
+    item: DocRef<'a, Item>,
+    trait_name: &str,
+) -> impl Iterator<Item = DocRef<'a, Item>> {
+    item.traits().filter(move |impl_block| {
+        matches!(
+            impl_block.inner(),
+            ItemEnum::Impl(impl_data) if impl_data.trait_.as_ref().is_some_and(|trait_| {
+                last_segment(&trait_.path) == trait_name
+            })
+        )
+    })
+}
+
+pub(super) fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Split `<Type as Trait>::rest` into `Type as Trait` and `::rest`
+fn split_qualified_self(path: &str) -> Option<(&str, &str)> {
+    let inner = path.strip_prefix('<')?;
+    let mut depth = 0;
+    let mut previous = None;
+    for (index, c) in inner.char_indices() {
+        match c {
+            '<' => depth += 1,
+            // `->` in a function pointer type does not close anything
+            '>' if previous == Some('-') => {}
+            '>' if depth == 0 => return Some((inner[..index].trim(), &inner[index + 1..])),
+            '>' => depth -= 1,
+            _ => {}
+        }
+        previous = Some(c);
+    }
+    None
+}
+
+/// Split `Type as Trait` at the `as` outside of any generic arguments
+fn split_as(qualified: &str) -> Option<(&str, &str)> {
+    let mut depth = 0usize;
+    for (index, c) in qualified.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ' ' if depth == 0 && qualified[index..].starts_with(" as ") => {
+                return Some((qualified[..index].trim(), qualified[index + 4..].trim()));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drop generic arguments, references and whitespace from a type or trait path, e.g.
+/// `&mut Vec<T>` to `Vec`
+fn strip_generics(path: &str) -> String {
+    let path = path.trim_start_matches('&').trim_start();
+    let path = path.strip_prefix("mut ").unwrap_or(path);
+    let mut stripped = String::new();
+    let mut depth = 0usize;
+    for c in path.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            c if depth == 0 && !c.is_whitespace() => stripped.push(c),
+            _ => {}
+        }
+    }
+    stripped
+}
@@ -0,0 +1,233 @@
+//! Putting concrete types in place of type parameters, to show a generic type's methods as
+//! they are for particular arguments, e.g. `Option<&str>`
+
+use super::Request;
+use super::implements::{Bindings, TypeExpr, substitute};
+use super::primitives::PRIMITIVES;
+use crate::doc_ref::DocRef;
+use rustdoc_types::{
+    Function, GenericArg, GenericArgs, GenericBound, GenericParamDefKind, Generics, Id, Item,
+    ItemEnum, Path, Type, WherePredicate,
+};
+
+impl Request {
+    /// What each of a type's type parameters stands for in a concrete type, like `T = u8`,
+    /// falling back to a parameter's default where the concrete type leaves it out
+    pub(crate) fn type_arguments(&self, item: &Item, concrete_type: &TypeExpr) -> Vec<String> {
+        let TypeExpr::Path { args, .. } = concrete_type else {
+            return vec![];
+        };
+        let Some(generics) = type_generics(item) else {
+            return vec![];
+        };
+
+        let mut args = args.iter();
+        generics
+            .params
+            .iter()
+            .filter_map(|param| {
+                let GenericParamDefKind::Type { default, .. } = &param.kind else {
+                    return None;
+                };
+                let name = &param.name;
+                match (args.next(), default) {
+                    (Some(arg), _) => Some(format!("{name} = {arg}")),
+                    (None, Some(default)) => {
+                        Some(format!("{name} = {} (default)", self.format_type(default)))
+                    }
+                    (None, None) => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Fill in the type arguments a concrete type leaves out with the defaults its type declares,
+/// e.g. `Vec<u8>` to `Vec<u8, alloc::alloc::Global>`, so that impls bounding them can be checked
+pub(super) fn with_default_args(item: DocRef<'_, Item>, concrete_type: &TypeExpr) -> TypeExpr {
+    let (TypeExpr::Path { path, args }, Some(generics)) = (concrete_type, type_generics(&item))
+    else {
+        return concrete_type.clone();
+    };
+
+    let mut given = args.iter();
+    let mut bindings = Bindings::new();
+    let mut args = vec![];
+    for param in &generics.params {
+        let GenericParamDefKind::Type { default, .. } = &param.kind else {
+            continue;
+        };
+        let arg = match (given.next(), default) {
+            (Some(arg), _) => arg.clone(),
+            (None, Some(default)) => match substitute(item, default, &bindings) {
+                Some(arg) => arg,
+                None => break,
+            },
+            (None, None) => break,
+        };
+        bindings.insert(param.name.clone(), arg.clone());
+        args.push(arg);
+    }
+    args.extend(given.cloned());
+
+    TypeExpr::Path {
+        path: path.clone(),
+        args,
+    }
+}
+
+/// The generics of a type or trait that can be given arguments
+pub(super) fn type_generics(item: &Item) -> Option<&Generics> {
+    match &item.inner {
+        ItemEnum::Trait(trait_) => Some(&trait_.generics),
+        ItemEnum::Struct(struct_data) => Some(&struct_data.generics),
+        ItemEnum::Enum(enum_data) => Some(&enum_data.generics),
+        ItemEnum::Union(union_data) => Some(&union_data.generics),
+        ItemEnum::TypeAlias(type_alias) => Some(&type_alias.generics),
+        _ => None,
+    }
+}
+
+/// The function with the bound type parameters replaced throughout its signature and where
+/// clause
+///
+/// A `self` parameter is left alone so that it still reads `&self` rather than the type.
+pub(crate) fn substitute_function(function: &Function, bindings: &Bindings) -> Function {
+    let mut function = function.clone();
+    for (name, type_) in &mut function.sig.inputs {
+        if name != "self" {
+            *type_ = substitute_type(type_, bindings);
+        }
+    }
+    if let Some(output) = &mut function.sig.output {
+        *output = substitute_type(output, bindings);
+    }
+    for predicate in &mut function.generics.where_predicates {
+        if let WherePredicate::BoundPredicate { type_, bounds, .. } = predicate {
+            *type_ = substitute_type(type_, bindings);
+            substitute_bounds(bounds, bindings);
+        }
+    }
+    function
+}
+
+/// The type with the bound type parameters replaced
+pub(crate) fn substitute_type(type_: &Type, bindings: &Bindings) -> Type {
+    let substitute = |type_: &Type| Box::new(substitute_type(type_, bindings));
+    match type_ {
+        Type::Generic(name) => match bindings.get(name) {
+            Some(bound) => to_type(bound),
+            None => type_.clone(),
+        },
+        Type::ResolvedPath(path) => Type::ResolvedPath(substitute_path(path, bindings)),
+        Type::BorrowedRef {
+            lifetime,
+            is_mutable,
+            type_,
+        } => Type::BorrowedRef {
+            lifetime: lifetime.clone(),
+            is_mutable: *is_mutable,
+            type_: substitute(type_),
+        },
+        Type::RawPointer { is_mutable, type_ } => Type::RawPointer {
+            is_mutable: *is_mutable,
+            type_: substitute(type_),
+        },
+        Type::Slice(type_) => Type::Slice(substitute(type_)),
+        Type::Array { type_, len } => Type::Array {
+            type_: substitute(type_),
+            len: len.clone(),
+        },
+        Type::Tuple(types) => Type::Tuple(
+            types
+                .iter()
+                .map(|type_| substitute_type(type_, bindings))
+                .collect(),
+        ),
+        Type::ImplTrait(bounds) => {
+            let mut bounds = bounds.clone();
+            substitute_bounds(&mut bounds, bindings);
+            Type::ImplTrait(bounds)
+        }
+        Type::QualifiedPath {
+            name,
+            args,
+            self_type,
+            trait_,
+        } => Type::QualifiedPath {
+            name: name.clone(),
+            args: args.clone(),
+            self_type: substitute(self_type),
+            trait_: trait_.as_ref().map(|path| substitute_path(path, bindings)),
+        },
+        _ => type_.clone(),
+    }
+}
+
+fn substitute_path(path: &Path, bindings: &Bindings) -> Path {
+    let mut path = path.clone();
+    if let Some(args) = path.args.as_deref_mut() {
+        match args {
+            GenericArgs::AngleBracketed { args, .. } => {
+                for arg in args {
+                    if let GenericArg::Type(type_) = arg {
+                        *type_ = substitute_type(type_, bindings);
+                    }
+                }
+            }
+            GenericArgs::Parenthesized { inputs, output } => {
+                for input in inputs {
+                    *input = substitute_type(input, bindings);
+                }
+                if let Some(output) = output {
+                    *output = substitute_type(output, bindings);
+                }
+            }
+            GenericArgs::ReturnTypeNotation => {}
+        }
+    }
+    path
+}
+
+fn substitute_bounds(bounds: &mut [GenericBound], bindings: &Bindings) {
+    for bound in bounds {
+        if let GenericBound::TraitBound { trait_, .. } = bound {
+            *trait_ = substitute_path(trait_, bindings);
+        }
+    }
+}
+
+/// A query type as a rustdoc type, for formatting
+///
+/// The paths are kept as the query wrote them, and have no id.
+fn to_type(type_: &TypeExpr) -> Type {
+    match type_ {
+        TypeExpr::Path { path, args } if args.is_empty() && PRIMITIVES.contains(&path.as_str()) => {
+            Type::Primitive(path.clone())
+        }
+        TypeExpr::Path { path, args } => Type::ResolvedPath(Path {
+            path: path.clone(),
+            id: Id(u32::MAX),
+            args: (!args.is_empty()).then(|| {
+                Box::new(GenericArgs::AngleBracketed {
+                    args: args
+                        .iter()
+                        .map(|arg| GenericArg::Type(to_type(arg)))
+                        .collect(),
+                    constraints: vec![],
+                })
+            }),
+        }),
+        TypeExpr::Reference { is_mutable, type_ } => Type::BorrowedRef {
+            lifetime: None,
+            is_mutable: *is_mutable,
+            type_: Box::new(to_type(type_)),
+        },
+        TypeExpr::Slice(type_) => Type::Slice(Box::new(to_type(type_))),
+        TypeExpr::Array(type_, len) => Type::Array {
+            type_: Box::new(to_type(type_)),
+            len: len.clone(),
+        },
+        TypeExpr::Tuple(types) => Type::Tuple(types.iter().map(to_type).collect()),
+    }
+}
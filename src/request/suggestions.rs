@@ -0,0 +1,126 @@
+use super::{Request, Suggestion};
+use crate::indexer::NameIndex;
+use crate::rustdoc::RUST_CRATES;
+
+/// How closely a name must match the one that was not found to be suggested from another module
+const MIN_NAME_SCORE: f64 = 0.9;
+
+/// Crate searched for a path whose first segment is not a crate, along with the workspace
+const STD_CRATE: &str = "std";
+
+impl Request {
+    /// Suggest items from anywhere in the crate that a failed path could have meant
+    ///
+    /// `resolve_path` only suggests siblings of the segment that was not found, so it cannot
+    /// find `tokio::sync::Mutex` for `tokio::Mutex`. This looks for items named like that
+    /// segment in the crate's [`NameIndex`], suggesting every path each is reachable at, plus
+    /// where it is defined if that is in another crate. Segments after the missing one
+    /// are kept, so `std::HashMap::new` suggests `std::collections::HashMap::new`.
+    ///
+    /// If the first segment is not a crate, it is looked for in the workspace crates, their
+    /// dependencies and std, among those whose docs are already built, so `Mutex` can suggest
+    /// `tokio::sync::Mutex` as well as `std::sync::Mutex`.
+    pub(crate) fn suggest_across_crates<'a>(
+        &'a self,
+        path: &str,
+        suggestions: &mut Vec<Suggestion<'a>>,
+    ) {
+        if path.starts_with('<') || path.contains(" as ") {
+            return;
+        }
+        let segments = path.split("::").collect::<Vec<_>>();
+        let Some(missing) = (0..segments.len()).find(|&index| {
+            self.resolve_path(&segments[..=index].join("::"), &mut vec![])
+                .is_none()
+        }) else {
+            return;
+        };
+
+        let (roots, name, rest) = if missing == 0 {
+            let project = &self.project;
+            // std re-exports what is public in core and alloc, so only std is searched
+            let dependencies = project
+                .available_crates()
+                .filter(|crate_name| {
+                    !project.is_workspace_package(*crate_name) && !RUST_CRATES.contains(crate_name)
+                })
+                .map(|crate_name| crate_name.to_string());
+            let roots = project
+                .workspace_packages()
+                .iter()
+                .cloned()
+                .chain(dependencies)
+                .chain([STD_CRATE.to_string()])
+                .filter_map(|crate_name| {
+                    let (json_path, _) =
+                        project.resolve_json_path(project.normalize_crate_name(&crate_name)?)?;
+                    let names = NameIndex::load_or_build(&json_path, || {
+                        self.built_crate_root(&crate_name)
+                    })?;
+                    Some((crate_name, names))
+                })
+                .collect::<Vec<_>>();
+            (roots, segments[0], &segments[1..])
+        } else {
+            let Some(root) = self.resolve_path(segments[0], &mut vec![]) else {
+                return;
+            };
+            let Some(names) = NameIndex::load_or_build(root.crate_docs().fs_path(), || Some(root))
+            else {
+                return;
+            };
+            (
+                vec![(segments[0].to_string(), names)],
+                segments[missing],
+                &segments[missing + 1..],
+            )
+        };
+
+        for (prefix, names) in &roots {
+            let mut matches = names.similar(name, MIN_NAME_SCORE).peekable();
+            // a crate is only loaded to check its matches, and never built for them
+            if missing == 0 && (matches.peek().is_none() || self.load_built(prefix).is_none()) {
+                continue;
+            }
+            let root_crate = self.resolve_path(prefix, &mut vec![]);
+            for (item_path, score) in matches {
+                let item_path = format!("{prefix}::{item_path}");
+                let Some(item) = self.resolve_path(&item_path, &mut vec![]) else {
+                    continue;
+                };
+                // where a re-export from another crate is defined, e.g. `alloc::rc::Rc`
+                let defined_at = root_crate
+                    .filter(|root| item.crate_docs().name() != root.crate_docs().name())
+                    .and_then(|_| item.path())
+                    .map(|path| path.to_string());
+
+                for item_path in [Some(item_path), defined_at].into_iter().flatten() {
+                    let mut full_path = item_path;
+                    for segment in rest {
+                        full_path.push_str("::");
+                        full_path.push_str(segment);
+                    }
+                    // a sibling suggestion scores the whole path, which undersells a matching
+                    // name
+                    if let Some(existing) = suggestions.iter_mut().find(|s| s.path == full_path) {
+                        existing.score = existing.score.max(score);
+                        continue;
+                    }
+
+                    let item = if rest.is_empty() {
+                        Some(item)
+                    } else {
+                        self.resolve_path(&full_path, &mut vec![])
+                    };
+                    if let Some(item) = item {
+                        suggestions.push(Suggestion {
+                            path: full_path,
+                            item: Some(item),
+                            score,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{Result, anyhow};
+use std::fmt::{self, Display, Formatter};
+
+/// A stable reference to a crate, module or item: `rustdoc://<crate>@<version>/<path>`
+///
+/// Path segments are separated by `/` rather than `::`. The version is omitted when rustdoc
+/// did not record one, as for some toolchain crates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResourceUri {
+    crate_name: String,
+    version: Option<String>,
+    segments: Vec<String>,
+}
+
+impl ResourceUri {
+    pub(crate) const SCHEME: &'static str = "rustdoc://";
+
+    /// URI template advertised to clients
+    pub(crate) const TEMPLATE: &'static str = "rustdoc://{crate}@{version}/{path}";
+
+    /// `version` is the crate version as rustdoc recorded it. Toolchain crates record the
+    /// compiler's, like `1.91.0-nightly (7ad23f43a 2025-09-09)`, of which only the release goes
+    /// in the URI.
+    pub(crate) fn new(
+        crate_name: impl Into<String>,
+        version: Option<&str>,
+        segments: Vec<String>,
+    ) -> Self {
+        Self {
+            crate_name: crate_name.into(),
+            version: version.map(|version| release(version).to_string()),
+            segments,
+        }
+    }
+
+    pub(crate) fn is_uri(name: &str) -> bool {
+        name.starts_with(Self::SCHEME)
+    }
+
+    pub(crate) fn parse(uri: &str) -> Result<Self> {
+        let rest = uri.strip_prefix(Self::SCHEME).ok_or_else(|| {
+            anyhow!(
+                "`{uri}` is not a rustdoc resource URI (expected `{}`)",
+                Self::TEMPLATE
+            )
+        })?;
+
+        let (crate_part, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let (crate_name, version) = match crate_part.split_once('@') {
+            Some((crate_name, version)) => (crate_name, Some(version)),
+            None => (crate_part, None),
+        };
+
+        if crate_name.is_empty() {
+            return Err(anyhow!("`{uri}` does not name a crate"));
+        }
+
+        Ok(Self::new(
+            crate_name,
+            version.filter(|version| !version.is_empty()),
+            path.split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+
+    pub(crate) fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    pub(crate) fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// The equivalent `::`-separated path, as accepted by `get_item`
+    pub(crate) fn item_path(&self) -> String {
+        let mut path = self.crate_name.clone();
+        for segment in &self.segments {
+            path.push_str("::");
+            path.push_str(segment);
+        }
+        path
+    }
+}
+
+/// The release part of a crate version, without the commit hash and date that toolchain crates
+/// record after it
+pub(crate) fn release(version: &str) -> &str {
+    version.split_whitespace().next().unwrap_or(version)
+}
+
+impl Display for ResourceUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", Self::SCHEME, self.crate_name)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{version}")?;
+        }
+        for segment in &self.segments {
+            write!(f, "/{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let uri = ResourceUri::parse("rustdoc://serde_json@1.0.143/value/Value").unwrap();
+        assert_eq!(uri.crate_name(), "serde_json");
+        assert_eq!(uri.version(), Some("1.0.143"));
+        assert_eq!(uri.item_path(), "serde_json::value::Value");
+        assert_eq!(uri.to_string(), "rustdoc://serde_json@1.0.143/value/Value");
+
+        let uri = ResourceUri::parse("rustdoc://std").unwrap();
+        assert_eq!(uri.version(), None);
+        assert_eq!(uri.item_path(), "std");
+        assert_eq!(uri.to_string(), "rustdoc://std");
+
+        let uri = ResourceUri::new(
+            "std",
+            Some("1.91.0-nightly\t(7ad23f43a\t2025-09-09)"),
+            vec!["vec".into()],
+        );
+        assert_eq!(uri.to_string(), "rustdoc://std@1.91.0-nightly/vec");
+
+        assert!(ResourceUri::parse("https://docs.rs/serde").is_err());
+        assert!(ResourceUri::parse("rustdoc://@1.0/foo").is_err());
+    }
+}
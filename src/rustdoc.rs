@@ -1,11 +1,14 @@
 use anyhow::{Result, anyhow};
-use cargo_metadata::{DependencyKind, Metadata, MetadataCommand};
+use cargo_metadata::{DependencyKind, Metadata, MetadataCommand, Package, Target};
 use cargo_toml::Manifest;
 use fieldwork::Fieldwork;
-use rustdoc_types::{Crate, FORMAT_VERSION, Id, Item};
-use serde::Deserialize;
-use std::collections::BTreeMap;
+use rustc_hash::FxHasher;
+use rustdoc_types::{Crate, FORMAT_VERSION, Id, Item, ItemEnum};
+use serde::{Deserialize, Serialize};
+use std::cell::{OnceCell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -13,10 +16,16 @@ use std::time::SystemTime;
 use walkdir::WalkDir;
 
 mod crate_name;
+mod load_error;
+mod watcher;
 
 use crate::doc_ref::{self, DocRef};
+use crate::fs_utils::FileLock;
 use crate::request::Request;
 use crate_name::CrateName;
+pub(crate) use load_error::{DocBuildError, LoadError};
+use watcher::ProjectWatcher;
+pub(crate) use watcher::{Notifier, WatchOptions};
 
 pub(crate) const RUST_CRATES: [CrateName<'_>; 5] = [
     CrateName("std"),
@@ -26,6 +35,9 @@ pub(crate) const RUST_CRATES: [CrateName<'_>; 5] = [
     CrateName("test"),
 ];
 
+/// Lock file in the target directory held while `cargo doc` runs
+const DOC_BUILD_LOCK: &str = "rustdoc-mcp-doc.lock";
+
 /// Manages a Cargo project and its rustdoc JSON files
 #[derive(Fieldwork)]
 #[fieldwork(get)]
@@ -40,6 +52,15 @@ pub(crate) struct RustdocProject {
     #[field = false]
     available_crates: Vec<String>,
     rustc_docs: Option<(PathBuf, String)>,
+    /// Hash of the manifests and lockfile this project was loaded from
+    #[field = false]
+    fingerprint: u64,
+    /// Notices changes to manifests and sources, if watching is enabled
+    #[field = false]
+    watcher: Option<ProjectWatcher>,
+    /// Workspace packages whose docs were found to be up to date since their sources last changed
+    #[field = false]
+    fresh_docs: RefCell<HashSet<String>>,
 }
 
 impl Debug for RustdocProject {
@@ -85,7 +106,7 @@ pub(crate) fn rustc_docs() -> Option<(PathBuf, String)> {
     path.exists().then_some((path, version))
 }
 
-fn eq_ignoring_dash_underscore(a: &str, b: &str) -> bool {
+pub(crate) fn eq_ignoring_dash_underscore(a: &str, b: &str) -> bool {
     let mut a = a.chars();
     let mut b = b.chars();
     loop {
@@ -137,8 +158,13 @@ impl RustdocProject {
             workspace_packages,
             available_crates: vec![],
             rustc_docs,
+            fingerprint: 0,
+            watcher: None,
+            fresh_docs: RefCell::default(),
         };
 
+        // after `cargo metadata`, which may have written the lockfile
+        project.fingerprint = project.compute_fingerprint();
         project.crate_info = project.generate_crate_info();
         project.available_crates = project
             .crate_info(None)
@@ -147,6 +173,78 @@ impl RustdocProject {
         Ok(project)
     }
 
+    /// Watch manifests and workspace sources, invalidating cached state as soon as they change
+    pub(crate) fn watch(&mut self, options: WatchOptions) -> Result<()> {
+        self.watcher = Some(ProjectWatcher::new(self, options)?);
+        Ok(())
+    }
+
+    /// Whether a manifest or the lockfile changed since loading, so this project must be reloaded
+    ///
+    /// When watching, files are only read after the watcher saw them change.
+    pub(crate) fn is_stale(&self) -> bool {
+        if let Some(watcher) = &self.watcher
+            && !watcher.take_manifest_changed()
+        {
+            return false;
+        }
+        self.compute_fingerprint() != self.fingerprint
+    }
+
+    /// Every manifest that affects metadata, and the lockfile
+    pub(crate) fn manifest_files(&self) -> Vec<PathBuf> {
+        let workspace_root = self.metadata.workspace_root.as_std_path();
+        let mut files = vec![
+            self.manifest_path.clone(),
+            workspace_root.join("Cargo.toml"),
+            workspace_root.join("Cargo.lock"),
+        ];
+        files.extend(
+            self.metadata
+                .workspace_packages()
+                .into_iter()
+                .map(|package| package.manifest_path.clone().into_std_path_buf()),
+        );
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    fn compute_fingerprint(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        for path in self.manifest_files() {
+            path.hash(&mut hasher);
+            std::fs::read(&path).ok().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// The directory containing a workspace package's manifest
+    pub(crate) fn manifest_dir(&self, package_name: &str) -> PathBuf {
+        self.metadata
+            .workspace_packages()
+            .into_iter()
+            .find(|package| eq_ignoring_dash_underscore(&package.name, package_name))
+            .and_then(|package| package.manifest_path.parent())
+            .map_or_else(|| self.project_root().to_path_buf(), Into::into)
+    }
+
+    /// The directory containing a workspace package's library root
+    pub(crate) fn source_dir(&self, package_name: &str) -> PathBuf {
+        self.metadata
+            .workspace_packages()
+            .into_iter()
+            .find(|package| eq_ignoring_dash_underscore(&package.name, package_name))
+            .and_then(|package| {
+                package
+                    .targets
+                    .iter()
+                    .find(|target| target.is_lib() || target.is_proc_macro())
+            })
+            .and_then(|target| target.src_path.parent())
+            .map_or_else(|| self.manifest_dir(package_name).join("src"), Into::into)
+    }
+
     pub(crate) fn resolve_json_path<'a>(
         &'a self,
         crate_name: CrateName<'a>,
@@ -185,28 +283,12 @@ impl RustdocProject {
     }
 
     /// Generate documentation for the project or a specific package
-    pub(crate) fn rebuild_docs(&self, crate_name: CrateName<'_>) -> Result<()> {
-        let project_root = self.project_root();
-
-        let output = Command::new("rustup")
-            .arg("run")
-            .args([
-                "nightly",
-                "cargo",
-                "doc",
-                "--no-deps",
-                "--package",
-                &*crate_name,
-            ])
-            .env("RUSTDOCFLAGS", "-Z unstable-options --output-format=json")
-            .current_dir(project_root)
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow!("cargo doc failed: {}", stderr));
-        }
-        Ok(())
+    pub(crate) fn rebuild_docs(&self, crate_name: CrateName<'_>) -> Result<(), DocBuildError> {
+        build_docs(
+            self.project_root(),
+            self.metadata.target_directory.as_std_path(),
+            &crate_name,
+        )
     }
 
     /// Get available crate names and optional descriptions
@@ -410,115 +492,406 @@ impl RustdocProject {
     }
 
     /// Load rustdoc data for a specific crate
-    pub(crate) fn load_crate(&self, crate_name: CrateName<'_>) -> Option<RustdocData> {
-        let (json_path, crate_type) = self.resolve_json_path(crate_name)?;
+    ///
+    /// Returns `Ok(None)` if the crate is not known to this project, and an error if it is known
+    /// but its documentation could not be produced.
+    pub(crate) fn load_crate(
+        &self,
+        crate_name: CrateName<'_>,
+    ) -> Result<Option<RustdocData>, LoadError> {
+        let Some((json_path, crate_type)) = self.resolve_json_path(crate_name) else {
+            return Ok(None);
+        };
 
         match crate_type {
             CrateType::Workspace => self.load_workspace(crate_name, json_path),
             CrateType::Library => self.load_dep(crate_name, json_path),
             CrateType::Rust => self.load_rustc(crate_name, json_path),
         }
+        .map(Some)
+    }
+
+    /// Load rustdoc data for a crate only if its JSON is already built and current
+    ///
+    /// Unlike [`load_crate`](Self::load_crate) this never runs `cargo doc`, for lookups that
+    /// are not worth a build, such as gathering suggestions.
+    pub(crate) fn load_built_crate(&self, crate_name: CrateName<'_>) -> Option<RustdocData> {
+        let (json_path, crate_type) = self.resolve_json_path(crate_name)?;
+        let (version, content) = match crate_type {
+            CrateType::Workspace if self.sources_newer_than(crate_name, &json_path) => return None,
+            CrateType::Workspace => self.read_workspace_json(crate_name, &json_path).ok()?,
+            _ => read_json(crate_name, &json_path).ok()?,
+        };
+        if version.format_version != FORMAT_VERSION {
+            return None;
+        }
+        parse_json(crate_name, json_path, &content, CrateType::Library).ok()
     }
 
     pub(crate) fn load_dep(
         &self,
         crate_name: CrateName<'_>,
         json_path: PathBuf,
-    ) -> Option<RustdocData> {
-        let mut tried_rebuilding = false;
+    ) -> Result<RustdocData, LoadError> {
         let expected_version = self
             .metadata
             .packages
             .iter()
             .find(|x| **x.name == *crate_name)
             .map(|x| x.version.to_string());
+        self.load_package(crate_name, &crate_name, expected_version, json_path)
+    }
 
-        loop {
-            if let Ok(content) = std::fs::read_to_string(&json_path)
-                && let Ok(RustdocVersion {
-                    format_version,
-                    crate_version,
-                }) = serde_json::from_str(&content)
-                && format_version == FORMAT_VERSION
-                && crate_version == expected_version
-            {
-                let crate_data: Crate = serde_json::from_str(&content).ok()?;
-
-                break Some(RustdocData {
-                    crate_data,
-                    name: crate_name.to_string(),
-                    crate_type: CrateType::Library,
-                    fs_path: json_path,
-                });
-            } else if !tried_rebuilding {
-                tried_rebuilding = true;
-                if self.rebuild_docs(crate_name).is_ok() {
-                    continue;
-                }
+    /// Load the docs of a crate that other docs refer to by its name in their `external_crates`
+    ///
+    /// That is the name of the crate's library, which can differ from both its package name and
+    /// the name a dependent gives it, as with `http1 = { package = "http", .. }`. The crate can be
+    /// anywhere in the dependency graph, including a dependency's own dependencies. `version`
+    /// picks between several versions of a package in the graph.
+    pub(crate) fn load_external_crate(
+        &self,
+        lib_name: &str,
+        version: Option<&str>,
+    ) -> Result<Option<RustdocData>, LoadError> {
+        let Some(crate_name) = CrateName::new(lib_name) else {
+            return Ok(None);
+        };
+        if RUST_CRATES.contains(&crate_name) {
+            return self.load_crate(crate_name);
+        }
+
+        let candidates = self.lib_packages(lib_name);
+        let Some((package, lib)) = pick_version(&candidates, version) else {
+            return Ok(None);
+        };
+
+        let crate_name = CrateName(&package.name);
+        if self.is_workspace_package(crate_name) {
+            return self.load_crate(crate_name);
+        }
+        let lib_name = lib.name.replace('-', "_");
+        let doc_dir = self.target_dir.join("doc");
+        let json_path = doc_dir.join(format!("{lib_name}.json"));
+        let package_spec = format!("{}@{}", package.name, package.version);
+        let version = package.version.to_string();
+
+        let several_versions = candidates
+            .iter()
+            .any(|(other, _)| other.version != package.version);
+        if !several_versions {
+            return self
+                .load_package(crate_name, &package_spec, Some(version), json_path)
+                .map(Some);
+        }
+
+        // every version of a package writes its docs to the same file, so each is kept under
+        // its version too, or loading one would rebuild the other every time
+        let versioned_path = doc_dir.join(format!("{lib_name}-{version}.json"));
+        if let Ok((found, content)) = read_json(crate_name, &versioned_path)
+            && found.format_version == FORMAT_VERSION
+            && found.crate_version.as_deref() == Some(&*version)
+        {
+            return parse_json(crate_name, versioned_path, &content, CrateType::Library).map(Some);
+        }
+        let crate_data =
+            self.load_package(crate_name, &package_spec, Some(version), json_path.clone())?;
+        match std::fs::copy(&json_path, &versioned_path) {
+            Ok(_) => Ok(Some(RustdocData {
+                fs_path: versioned_path,
+                ..crate_data
+            })),
+            Err(error) => {
+                log::warn!(
+                    "could not keep {} as {}: {error}",
+                    json_path.display(),
+                    versioned_path.display()
+                );
+                Ok(Some(crate_data))
             }
-            break None;
         }
     }
 
-    fn load_rustc(&self, crate_name: CrateName<'_>, json_path: PathBuf) -> Option<RustdocData> {
-        if let Ok(content) = std::fs::read_to_string(&json_path)
-            && let Ok(RustdocVersion { format_version, .. }) = serde_json::from_str(&content)
-            && format_version == FORMAT_VERSION
-        {
-            let crate_data: Crate = serde_json::from_str(&content).ok()?;
+    /// The key a crate's docs are cached under while handling a request: its package and version,
+    /// like `serde@1.0.219`, or its name for crates without a package like std
+    ///
+    /// Loading a crate by name and by another crate's reference to it give the same key, so that
+    /// its docs are only parsed once.
+    pub(crate) fn crate_key(&self, crate_name: CrateName<'_>) -> String {
+        self.metadata
+            .packages
+            .iter()
+            .find(|package| !RUST_CRATES.contains(&crate_name) && **package.name == *crate_name)
+            .map_or_else(
+                || crate_name.to_string(),
+                |package| format!("{}@{}", package.name, package.version),
+            )
+    }
 
-            Some(RustdocData {
-                crate_data,
-                name: crate_name.to_string(),
-                crate_type: CrateType::Library,
-                fs_path: json_path,
+    /// The key that the crate [`load_external_crate`](Self::load_external_crate) loads for
+    /// `lib_name` and `version` is cached under, see [`crate_key`](Self::crate_key)
+    pub(crate) fn external_crate_key(&self, lib_name: &str, version: Option<&str>) -> String {
+        match CrateName::new(lib_name) {
+            Some(crate_name) if RUST_CRATES.contains(&crate_name) => self.crate_key(crate_name),
+            _ => match pick_version(&self.lib_packages(lib_name), version) {
+                Some((package, _)) => format!("{}@{}", package.name, package.version),
+                None => lib_name.to_string(),
+            },
+        }
+    }
+
+    /// The packages whose library is named `lib_name`, with that library
+    fn lib_packages(&self, lib_name: &str) -> Vec<(&Package, &Target)> {
+        self.metadata
+            .packages
+            .iter()
+            .filter_map(|package| {
+                let lib = package.targets.iter().find(|target| {
+                    (target.is_lib() || target.is_proc_macro())
+                        && eq_ignoring_dash_underscore(&target.name, lib_name)
+                })?;
+                Some((package, lib))
             })
-        } else {
-            None
+            .collect()
+    }
+
+    /// Load a dependency's docs, building them if they are missing, stale or for another version
+    /// than `expected_version`
+    fn load_package(
+        &self,
+        crate_name: CrateName<'_>,
+        package_spec: &str,
+        expected_version: Option<String>,
+        json_path: PathBuf,
+    ) -> Result<RustdocData, LoadError> {
+        let mut tried_rebuilding = false;
+        loop {
+            match read_json(crate_name, &json_path) {
+                Ok((version, content))
+                    if version.format_version == FORMAT_VERSION
+                        && version.crate_version == expected_version =>
+                {
+                    break parse_json(crate_name, json_path, &content, CrateType::Library);
+                }
+                result if tried_rebuilding => {
+                    break Err(stale_json_error(crate_name, json_path, result));
+                }
+                _ => {
+                    tried_rebuilding = true;
+                    build_docs(
+                        self.project_root(),
+                        self.metadata.target_directory.as_std_path(),
+                        package_spec,
+                    )?;
+                }
+            }
+        }
+    }
+
+    fn load_rustc(
+        &self,
+        crate_name: CrateName<'_>,
+        json_path: PathBuf,
+    ) -> Result<RustdocData, LoadError> {
+        match read_json(crate_name, &json_path) {
+            Ok((version, content)) if version.format_version == FORMAT_VERSION => {
+                parse_json(crate_name, json_path, &content, CrateType::Library)
+            }
+            result => Err(stale_json_error(crate_name, json_path, result)),
         }
     }
 
-    fn load_workspace(&self, crate_name: CrateName<'_>, json_path: PathBuf) -> Option<RustdocData> {
+    fn load_workspace(
+        &self,
+        crate_name: CrateName<'_>,
+        json_path: PathBuf,
+    ) -> Result<RustdocData, LoadError> {
         let mut tried_rebuilding = false;
         loop {
-            let needs_rebuild = json_path
-                .metadata()
-                .ok()
-                .and_then(|m| m.modified().ok())
-                .is_none_or(|docs_updated| {
-                    WalkDir::new(self.project_root().join("src"))
-                        .into_iter()
-                        .filter_map(|entry| -> Option<SystemTime> {
-                            entry.ok()?.metadata().ok()?.modified().ok()
-                        })
-                        .any(|file_updated| file_updated > docs_updated)
-                });
-
-            if !needs_rebuild
-                && let Ok(content) = std::fs::read_to_string(&json_path)
-                && let Ok(RustdocVersion { format_version, .. }) = serde_json::from_str(&content)
-                && format_version == FORMAT_VERSION
-            {
-                let crate_data: Crate = serde_json::from_str(&content).ok()?;
-
-                break Some(RustdocData {
-                    crate_data,
-                    name: crate_name.to_string(),
-                    crate_type: CrateType::Library,
-                    fs_path: json_path,
-                });
-            } else if !tried_rebuilding {
-                tried_rebuilding = true;
-                if self.rebuild_docs(crate_name).is_ok() {
-                    continue;
+            let needs_rebuild = match &self.watcher {
+                // Once docs are known to be fresh, only the watcher can make them stale
+                Some(watcher) if self.fresh_docs.borrow().contains(&*crate_name) => {
+                    watcher.take_changed(&crate_name) || !json_path.exists()
+                }
+                _ => self.sources_newer_than(crate_name, &json_path),
+            };
+
+            match self.read_workspace_json(crate_name, &json_path) {
+                Ok((version, content))
+                    if (!needs_rebuild || tried_rebuilding)
+                        && version.format_version == FORMAT_VERSION =>
+                {
+                    self.fresh_docs.borrow_mut().insert(crate_name.to_string());
+                    break parse_json(crate_name, json_path, &content, CrateType::Library);
+                }
+                result if tried_rebuilding => {
+                    break Err(stale_json_error(crate_name, json_path, result));
+                }
+                _ => {
+                    tried_rebuilding = true;
+                    if let Some(watcher) = &self.watcher {
+                        // this build covers any change seen so far
+                        watcher.take_changed(&crate_name);
+                    }
+                    self.rebuild_docs(crate_name)?;
                 }
             }
-            break None;
         }
     }
+
+    /// Read a workspace package's JSON once no `cargo doc` is writing it, as one regenerating
+    /// docs in the background may be
+    fn read_workspace_json(
+        &self,
+        crate_name: CrateName<'_>,
+        json_path: &Path,
+    ) -> Result<(RustdocVersion, String), LoadError> {
+        let _lock = FileLock::acquire_shared_or_warn(
+            &self
+                .metadata
+                .target_directory
+                .as_std_path()
+                .join(DOC_BUILD_LOCK),
+        );
+        read_json(crate_name, json_path)
+    }
+
+    /// Whether any source of a workspace package was modified after its docs were generated
+    fn sources_newer_than(&self, crate_name: CrateName<'_>, json_path: &Path) -> bool {
+        json_path
+            .metadata()
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .is_none_or(|docs_updated| {
+                WalkDir::new(self.source_dir(&crate_name))
+                    .into_iter()
+                    .filter_map(|entry| -> Option<SystemTime> {
+                        entry.ok()?.metadata().ok()?.modified().ok()
+                    })
+                    .any(|file_updated| file_updated > docs_updated)
+            })
+    }
 }
 
-#[derive(Debug, Clone, Fieldwork)]
+/// The package of the version asked for, or the first if that version is not among them
+fn pick_version<'a>(
+    candidates: &[(&'a Package, &'a Target)],
+    version: Option<&str>,
+) -> Option<(&'a Package, &'a Target)> {
+    candidates
+        .iter()
+        .find(|(package, _)| version.is_some_and(|v| package.version.to_string() == v))
+        .or(candidates.first())
+        .copied()
+}
+
+/// Run `cargo doc` to produce rustdoc JSON for one package
+///
+/// Builds are serialized through a lock in cargo's target directory, so that several servers
+/// working on one project do not run `cargo doc` over each other.
+pub(crate) fn build_docs(
+    project_root: &Path,
+    target_dir: &Path,
+    crate_name: &str,
+) -> Result<(), DocBuildError> {
+    let rustdocflags = "-Z unstable-options --output-format=json";
+    let args = [
+        "run",
+        "nightly",
+        "cargo",
+        "doc",
+        "--no-deps",
+        "--package",
+        crate_name,
+    ];
+    let command = format!("RUSTDOCFLAGS=\"{rustdocflags}\" rustup {}", args.join(" "));
+
+    let _lock = FileLock::acquire_or_warn(&target_dir.join(DOC_BUILD_LOCK));
+    let output = Command::new("rustup")
+        .args(args)
+        .env("RUSTDOCFLAGS", rustdocflags)
+        .current_dir(project_root)
+        .output()
+        .map_err(|error| DocBuildError::spawn_failed(crate_name, command.clone(), error))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        return Err(DocBuildError::new(
+            crate_name,
+            command,
+            output.status.code(),
+            stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Read a rustdoc JSON file along with its version header
+fn read_json(
+    crate_name: CrateName<'_>,
+    json_path: &Path,
+) -> Result<(RustdocVersion, String), LoadError> {
+    let content = std::fs::read_to_string(json_path).map_err(|_| LoadError::MissingJson {
+        crate_name: crate_name.to_string(),
+        json_path: json_path.to_owned(),
+    })?;
+
+    let version = serde_json::from_str(&content).map_err(|error| LoadError::InvalidJson {
+        crate_name: crate_name.to_string(),
+        json_path: json_path.to_owned(),
+        message: error.to_string(),
+    })?;
+
+    Ok((version, content))
+}
+
+fn parse_json(
+    crate_name: CrateName<'_>,
+    json_path: PathBuf,
+    content: &str,
+    crate_type: CrateType,
+) -> Result<RustdocData, LoadError> {
+    match serde_json::from_str(content) {
+        Ok(crate_data) => Ok(RustdocData {
+            crate_data,
+            name: crate_name.to_string(),
+            crate_type,
+            fs_path: json_path,
+            primitives: OnceCell::new(),
+        }),
+        Err(error) => Err(LoadError::InvalidJson {
+            crate_name: crate_name.to_string(),
+            json_path,
+            message: error.to_string(),
+        }),
+    }
+}
+
+/// Explain why JSON that is still unusable (after rebuilding, if possible) was rejected
+fn stale_json_error(
+    crate_name: CrateName<'_>,
+    json_path: PathBuf,
+    result: Result<(RustdocVersion, String), LoadError>,
+) -> LoadError {
+    match result {
+        Ok((version, _)) if version.format_version != FORMAT_VERSION => LoadError::FormatVersion {
+            crate_name: crate_name.to_string(),
+            json_path,
+            found: version.format_version,
+            expected: FORMAT_VERSION,
+        },
+        Ok((version, _)) => LoadError::InvalidJson {
+            crate_name: crate_name.to_string(),
+            json_path,
+            message: format!(
+                "documented crate version {} does not match the locked version",
+                version.crate_version.as_deref().unwrap_or("(none)")
+            ),
+        },
+        Err(error) => error,
+    }
+}
+
+#[derive(Debug, Clone, Fieldwork, Serialize)]
 #[fieldwork(get, rename_predicates)]
 pub(crate) struct CrateInfo {
     crate_type: CrateType,
@@ -536,7 +909,8 @@ struct RustdocVersion {
     crate_version: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub(crate) enum CrateType {
     Workspace,
     Library,
@@ -559,6 +933,10 @@ pub(crate) struct RustdocData {
     crate_type: CrateType,
 
     fs_path: PathBuf,
+
+    /// The primitive types this crate documents, by name, found on first lookup
+    #[field = false]
+    primitives: OnceCell<HashMap<String, Id>>,
 }
 
 impl Debug for RustdocData {
@@ -588,4 +966,22 @@ impl RustdocData {
     pub(crate) fn path<'a>(&'a self, id: &Id) -> Option<doc_ref::Path<'a>> {
         self.paths.get(id).map(|summary| summary.into())
     }
+
+    /// The primitive type this crate documents as `name`, like `str` in std and core
+    pub(crate) fn primitive<'a>(
+        &'a self,
+        request: &'a Request,
+        name: &str,
+    ) -> Option<DocRef<'a, Item>> {
+        let primitives = self.primitives.get_or_init(|| {
+            self.index
+                .iter()
+                .filter_map(|(id, item)| match &item.inner {
+                    ItemEnum::Primitive(primitive) => Some((primitive.name.clone(), *id)),
+                    _ => None,
+                })
+                .collect()
+        });
+        self.get(request, primitives.get(name)?)
+    }
 }
@@ -0,0 +1,273 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+/// How many trailing lines of cargo's stderr to include when displaying a build failure
+const STDERR_DISPLAY_LINES: usize = 60;
+
+/// Why rustdoc JSON for a crate could not be loaded
+#[derive(Debug, Clone)]
+pub(crate) enum LoadError {
+    /// `cargo doc` could not be run or exited unsuccessfully
+    DocBuild(DocBuildError),
+
+    /// The JSON was produced by a rustdoc with a different format version than we understand
+    FormatVersion {
+        crate_name: String,
+        json_path: PathBuf,
+        found: u32,
+        expected: u32,
+    },
+
+    /// The JSON file exists but could not be read or parsed
+    InvalidJson {
+        crate_name: String,
+        json_path: PathBuf,
+        message: String,
+    },
+
+    /// `cargo doc` succeeded but did not leave usable JSON where we expected it
+    MissingJson {
+        crate_name: String,
+        json_path: PathBuf,
+    },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::DocBuild(error) => Display::fmt(error, f),
+            LoadError::FormatVersion {
+                crate_name,
+                json_path,
+                found,
+                expected,
+            } => write!(
+                f,
+                "Could not load docs for `{crate_name}`: {} uses rustdoc JSON format version \
+                 {found}, but this server understands version {expected}.\n\
+                 Likely cause: the installed nightly toolchain does not match this version of \
+                 rustdoc-mcp. Update rustdoc-mcp or install a matching nightly.",
+                json_path.display()
+            ),
+            LoadError::InvalidJson {
+                crate_name,
+                json_path,
+                message,
+            } => write!(
+                f,
+                "Could not load docs for `{crate_name}`: failed to read {}: {message}",
+                json_path.display()
+            ),
+            LoadError::MissingJson {
+                crate_name,
+                json_path,
+            } => write!(
+                f,
+                "Could not load docs for `{crate_name}`: cargo doc succeeded but {} was not \
+                 written.\nLikely cause: the package builds no library target, so there are no \
+                 library docs to show.",
+                json_path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<DocBuildError> for LoadError {
+    fn from(value: DocBuildError) -> Self {
+        Self::DocBuild(value)
+    }
+}
+
+/// A failed attempt to generate rustdoc JSON with `cargo doc`
+#[derive(Debug, Clone)]
+pub(crate) struct DocBuildError {
+    pub(crate) crate_name: String,
+    /// The command line that was run, including relevant environment
+    pub(crate) command: String,
+    /// Exit code, if the command ran to completion
+    pub(crate) status: Option<i32>,
+    /// Everything cargo wrote to stderr
+    pub(crate) stderr: String,
+    pub(crate) cause: BuildFailureCause,
+}
+
+impl DocBuildError {
+    /// Classify a failure from the captured stderr
+    pub(crate) fn new(
+        crate_name: &str,
+        command: String,
+        status: Option<i32>,
+        stderr: String,
+    ) -> Self {
+        let cause = BuildFailureCause::detect(&stderr);
+        Self {
+            crate_name: crate_name.to_string(),
+            command,
+            status,
+            stderr,
+            cause,
+        }
+    }
+
+    /// The command could not be started at all
+    pub(crate) fn spawn_failed(crate_name: &str, command: String, error: std::io::Error) -> Self {
+        Self {
+            crate_name: crate_name.to_string(),
+            command,
+            status: None,
+            stderr: error.to_string(),
+            cause: BuildFailureCause::ToolchainMissing,
+        }
+    }
+}
+
+impl Display for DocBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Failed to build docs for `{}`.", self.crate_name)?;
+        writeln!(f, "Likely cause: {}", self.cause)?;
+        writeln!(f, "Command: {}", self.command)?;
+        if let Some(status) = self.status {
+            writeln!(f, "Exit status: {status}")?;
+        }
+
+        let lines = self.stderr.lines().collect::<Vec<_>>();
+        if !lines.is_empty() {
+            f.write_str("\nstderr:\n")?;
+            let skipped = lines.len().saturating_sub(STDERR_DISPLAY_LINES);
+            if skipped > 0 {
+                writeln!(f, "[{skipped} earlier lines elided]")?;
+            }
+            for line in &lines[skipped..] {
+                writeln!(f, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DocBuildError {}
+
+/// Best guess at why `cargo doc` failed, derived from its output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BuildFailureCause {
+    /// rustup or the nightly toolchain is not installed
+    ToolchainMissing,
+    /// Dependencies need to be downloaded but the network is unavailable
+    NetworkRequired,
+    /// A build script could not find a native library
+    MissingSystemLibrary,
+    /// The crate or one of its dependencies does not compile
+    CompileError,
+    Unknown,
+}
+
+impl BuildFailureCause {
+    fn detect(stderr: &str) -> Self {
+        let contains_any = |needles: &[&str]| needles.iter().any(|n| stderr.contains(n));
+
+        if contains_any(&[
+            "toolchain 'nightly",
+            "is not installed",
+            "no such command: `doc`",
+        ]) {
+            Self::ToolchainMissing
+        } else if contains_any(&[
+            "failed to download",
+            "Unable to update registry",
+            "failed to get `",
+            "Couldn't resolve host",
+            "Could not resolve host",
+            "failed to fetch",
+            "attempting to make an HTTP request",
+            "--offline",
+        ]) {
+            Self::NetworkRequired
+        } else if contains_any(&[
+            "pkg-config",
+            "could not find system library",
+            "The system library",
+            "unable to find library",
+            "linking with `cc` failed",
+        ]) {
+            Self::MissingSystemLibrary
+        } else if contains_any(&[
+            "error[E",
+            "could not compile",
+            "error: expected",
+            "error: cannot find",
+        ]) {
+            Self::CompileError
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl Display for BuildFailureCause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ToolchainMissing => {
+                "the nightly toolchain is not available. Install it with `rustup toolchain install nightly`."
+            }
+            Self::NetworkRequired => {
+                "dependencies need to be downloaded but the network is unavailable. Run `cargo fetch` while online, or vendor the dependencies."
+            }
+            Self::MissingSystemLibrary => {
+                "a build script could not find a native system library. Install the library (and its development headers) named in the output below."
+            }
+            Self::CompileError => {
+                "the crate does not compile. Fix the errors shown below; retrying with a different item name will not help."
+            }
+            Self::Unknown => "unrecognized cargo failure; see the output below.",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_failure_causes() {
+        assert_eq!(
+            BuildFailureCause::detect("error[E0425]: cannot find value `x` in this scope"),
+            BuildFailureCause::CompileError
+        );
+        assert_eq!(
+            BuildFailureCause::detect(
+                "error: failed to download from `https://index.crates.io/config.json`"
+            ),
+            BuildFailureCause::NetworkRequired
+        );
+        assert_eq!(
+            BuildFailureCause::detect(
+                "thread 'main' panicked: The system library `openssl` required by crate \
+                 `openssl-sys` was not found. The file `openssl.pc` needs to be installed and \
+                 the PKG_CONFIG_PATH environment variable must contain its parent directory."
+            ),
+            BuildFailureCause::MissingSystemLibrary
+        );
+        assert_eq!(
+            BuildFailureCause::detect("error: toolchain 'nightly-x86_64' is not installed"),
+            BuildFailureCause::ToolchainMissing
+        );
+        assert_eq!(
+            BuildFailureCause::detect("something odd"),
+            BuildFailureCause::Unknown
+        );
+    }
+
+    #[test]
+    fn display_elides_long_stderr() {
+        let stderr = (0..100).map(|i| format!("line {i}\n")).collect::<String>();
+        let error = DocBuildError::new("foo", "cargo doc".into(), Some(101), stderr);
+        let displayed = error.to_string();
+        assert!(displayed.contains("Command: cargo doc"));
+        assert!(displayed.contains("Exit status: 101"));
+        assert!(displayed.contains("[40 earlier lines elided]"));
+        assert!(!displayed.contains("line 39\n"));
+        assert!(displayed.contains("line 99"));
+    }
+}
@@ -0,0 +1,369 @@
+use super::{RustdocProject, build_docs};
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long to wait for further changes before regenerating, so that a burst of saves
+/// produces one build
+const REGENERATE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Receives the method name of a notification to send to connected clients
+pub(crate) type Notifier = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// What to do when a watched project changes, beyond invalidating what is cached
+#[derive(Clone, Default)]
+pub(crate) struct WatchOptions {
+    /// Start rebuilding docs for changed packages immediately, rather than on next use
+    pub(crate) regenerate: bool,
+    /// Tell clients that resources changed
+    pub(crate) notifier: Option<Notifier>,
+}
+
+impl Debug for WatchOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchOptions")
+            .field("regenerate", &self.regenerate)
+            .field("notifier", &self.notifier.is_some())
+            .finish()
+    }
+}
+
+/// A workspace package and the files its docs are generated from
+#[derive(Debug, Clone)]
+struct WatchedPackage {
+    name: String,
+    /// Contains the manifest, build script and any files the crate includes
+    manifest_dir: PathBuf,
+    /// Contains the library root and its modules
+    source_dir: PathBuf,
+}
+
+/// Watches a project's manifests and workspace sources so that stale state is noticed as soon
+/// as files change, rather than by scanning on every load
+pub(crate) struct ProjectWatcher {
+    manifest_changed: Arc<AtomicBool>,
+    changed_packages: Arc<Mutex<HashSet<String>>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl Debug for ProjectWatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProjectWatcher")
+            .field("manifest_changed", &self.manifest_changed)
+            .field("changed_packages", &self.changed_packages)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProjectWatcher {
+    pub(crate) fn new(project: &RustdocProject, options: WatchOptions) -> Result<Self> {
+        let manifest_changed = Arc::new(AtomicBool::new(false));
+        let changed_packages = Arc::new(Mutex::new(HashSet::new()));
+        let project_root = project.project_root().to_path_buf();
+        let doc_dir = project.target_dir().join("doc");
+
+        let packages = project
+            .workspace_packages()
+            .iter()
+            .map(|name| WatchedPackage {
+                name: name.clone(),
+                manifest_dir: project.manifest_dir(name),
+                source_dir: project.source_dir(name),
+            })
+            .collect::<Vec<_>>();
+
+        let regenerate = options.regenerate.then(|| {
+            spawn_regenerator(
+                project_root.clone(),
+                project
+                    .metadata()
+                    .target_directory
+                    .clone()
+                    .into_std_path_buf(),
+                Arc::clone(&changed_packages),
+                options.notifier.clone(),
+            )
+        });
+
+        let mut watcher = {
+            let manifest_changed = Arc::clone(&manifest_changed);
+            let changed_packages = Arc::clone(&changed_packages);
+            let packages = packages.clone();
+            let target_dir = project.target_dir().to_path_buf();
+
+            RecommendedWatcher::new(
+                move |res: Result<Event, notify::Error>| {
+                    let Ok(event) = res else { return };
+                    if !matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        return;
+                    }
+                    log::trace!("received {event:?}");
+
+                    let mut changed = false;
+                    for path in &event.paths {
+                        if path.starts_with(&target_dir) {
+                            continue;
+                        }
+
+                        if is_manifest(path) {
+                            changed |= !manifest_changed.swap(true, Ordering::SeqCst);
+                        } else if let Some(package) = package_for(&packages, path) {
+                            let newly_changed = changed_packages
+                                .lock()
+                                .is_ok_and(|mut changed| changed.insert(package.name.clone()));
+
+                            if newly_changed {
+                                changed = true;
+                                let docs = doc_dir.join(package.name.replace('-', "_"));
+                                for index in ["index", "names"] {
+                                    let _ = std::fs::remove_file(docs.with_extension(index));
+                                }
+                            }
+
+                            if let Some(regenerate) = &regenerate {
+                                let _ = regenerate.send(package.name.clone());
+                            }
+                        }
+                    }
+
+                    if changed && let Some(notifier) = &options.notifier {
+                        notifier("notifications/resources/list_changed");
+                    }
+                },
+                notify::Config::default(),
+            )?
+        };
+
+        let mut manifest_dirs = project
+            .manifest_files()
+            .iter()
+            .filter_map(|path| path.parent().map(Path::to_path_buf))
+            .collect::<Vec<_>>();
+        manifest_dirs.sort();
+        manifest_dirs.dedup();
+        for dir in &manifest_dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        for package in &packages {
+            if package.source_dir.exists() {
+                watcher.watch(&package.source_dir, RecursiveMode::Recursive)?;
+            }
+        }
+
+        Ok(Self {
+            manifest_changed,
+            changed_packages,
+            _watcher: watcher,
+        })
+    }
+
+    /// Whether a `Cargo.toml` or `Cargo.lock` was touched since this was last called
+    pub(crate) fn take_manifest_changed(&self) -> bool {
+        self.manifest_changed.swap(false, Ordering::SeqCst)
+    }
+
+    /// Whether sources of a workspace package changed since this was last called for it
+    pub(crate) fn take_changed(&self, package: &str) -> bool {
+        self.changed_packages
+            .lock()
+            .is_ok_and(|mut changed| changed.remove(package))
+    }
+}
+
+fn is_manifest(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == "Cargo.toml" || name == "Cargo.lock")
+}
+
+/// The package whose docs a changed file may affect
+fn package_for<'a>(packages: &'a [WatchedPackage], path: &Path) -> Option<&'a WatchedPackage> {
+    let hidden = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'));
+    if hidden {
+        return None;
+    }
+
+    packages
+        .iter()
+        .find(|package| path.starts_with(&package.source_dir))
+        .or_else(|| {
+            packages
+                .iter()
+                .find(|package| path.parent() == Some(&package.manifest_dir))
+        })
+}
+
+/// Rebuild docs for changed packages on a background thread, once changes settle
+///
+/// A package whose docs were rebuilt is no longer marked changed, so loading it does not build
+/// them again. The thread exits when the watcher, and with it the sender, is dropped.
+fn spawn_regenerator(
+    project_root: PathBuf,
+    target_dir: PathBuf,
+    changed_packages: Arc<Mutex<HashSet<String>>>,
+    notifier: Option<Notifier>,
+) -> Sender<String> {
+    let (sender, receiver) = mpsc::channel::<String>();
+
+    std::thread::spawn(move || {
+        while let Ok(package) = receiver.recv() {
+            let mut pending = HashSet::from([package]);
+            loop {
+                match receiver.recv_timeout(REGENERATE_DEBOUNCE) {
+                    Ok(package) => {
+                        pending.insert(package);
+                    }
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+
+            for package in pending {
+                log::debug!("regenerating docs for {package}");
+                match build_docs(&project_root, &target_dir, &package) {
+                    // a change made during the build is sent again, so it gets its own build
+                    Ok(()) => {
+                        if let Ok(mut changed) = changed_packages.lock() {
+                            changed.remove(&package);
+                        }
+                    }
+                    Err(error) => log::warn!("{error}"),
+                }
+            }
+
+            if let Some(notifier) = &notifier {
+                notifier("notifications/resources/list_changed");
+            }
+        }
+    });
+
+    sender
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Instant;
+
+    fn wait_for(mut condition: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if condition() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+
+    #[test]
+    fn source_and_manifest_changes_are_noticed() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"watched\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn one() {}\n").unwrap();
+
+        let notifications = Arc::new(AtomicUsize::new(0));
+        let notifier: Notifier = {
+            let notifications = Arc::clone(&notifications);
+            Arc::new(move |method| {
+                assert_eq!(method, "notifications/resources/list_changed");
+                notifications.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        let mut project = RustdocProject::load(root.join("Cargo.toml")).unwrap();
+        project
+            .watch(WatchOptions {
+                regenerate: false,
+                notifier: Some(notifier),
+            })
+            .unwrap();
+        let watcher = project.watcher.as_ref().unwrap();
+
+        std::fs::create_dir_all(root.join("target/doc")).unwrap();
+        std::fs::write(root.join("target/doc/watched.json"), "{}").unwrap();
+        std::fs::write(root.join("target/doc/watched.index"), "").unwrap();
+        std::fs::write(root.join("target/doc/watched.names"), "").unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(!watcher.take_changed("watched"), "target is not watched");
+
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "pub fn one() {}\npub fn two() {}\n",
+        )
+        .unwrap();
+        assert!(wait_for(|| watcher.take_changed("watched")));
+        assert!(!root.join("target/doc/watched.index").exists());
+        assert!(!root.join("target/doc/watched.names").exists());
+        assert!(!project.is_stale());
+
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"watched\"\nversion = \"0.2.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        assert!(wait_for(|| project.is_stale()));
+        assert!(notifications.load(Ordering::SeqCst) >= 2);
+    }
+
+    #[test]
+    fn regenerated_packages_are_not_rebuilt_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"regenerated\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn one() {}\n").unwrap();
+
+        let mut project = RustdocProject::load(root.join("Cargo.toml")).unwrap();
+        project
+            .watch(WatchOptions {
+                regenerate: true,
+                notifier: None,
+            })
+            .unwrap();
+        let watcher = project.watcher.as_ref().unwrap();
+
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "pub fn one() {}\npub fn two() {}\n",
+        )
+        .unwrap();
+        let json_path = root.join("target/doc/regenerated.json");
+        let regenerated = || {
+            std::fs::read_to_string(&json_path).is_ok_and(|json| json.contains("\"two\""))
+                && !watcher
+                    .changed_packages
+                    .lock()
+                    .unwrap()
+                    .contains("regenerated")
+        };
+        let deadline = Instant::now() + Duration::from_secs(120);
+        while !regenerated() {
+            assert!(Instant::now() < deadline, "docs were not regenerated");
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        assert!(!watcher.take_changed("regenerated"));
+    }
+}
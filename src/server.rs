@@ -0,0 +1,302 @@
+use crate::rustdoc::{Notifier, WatchOptions};
+use crate::state::RustdocTools;
+use crate::tools::Tools;
+use anyhow::Result;
+use env_logger::{Builder, Target};
+use mcplease::types::{Info, McpRequest, McpResponse};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::fs::{self, OpenOptions};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod http;
+mod resources;
+mod stdio;
+
+/// The MCP protocol revision this server speaks
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeResponse<'a> {
+    protocol_version: &'static str,
+    capabilities: Value,
+    server_info: &'a Info,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instructions: Option<&'static str>,
+}
+
+/// Options for `rustdoc-mcp serve`
+#[derive(Debug, clap::Parser)]
+#[command(name = "rustdoc-mcp serve")]
+pub(crate) struct ServeArgs {
+    /// Serve streamable HTTP at this address (e.g. 127.0.0.1:8080) instead of stdio, so that
+    /// several clients can share one server and its loaded crates
+    #[arg(long)]
+    http: Option<SocketAddr>,
+
+    /// Don't watch projects for changes; staleness is then checked when docs are loaded
+    #[arg(long)]
+    no_watch: bool,
+
+    /// Rebuild docs as soon as sources change, rather than when they are next requested
+    #[arg(long, conflicts_with = "no_watch")]
+    regenerate: bool,
+}
+
+/// Log to the file named by `MCP_LOG_LOCATION`, if set, since stdout carries the protocol
+pub(crate) fn init_logging() -> Result<()> {
+    if let Ok(log_location) = std::env::var("MCP_LOG_LOCATION") {
+        let path = PathBuf::from(&*shellexpand::tilde(&log_location));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Builder::from_default_env()
+            .target(Target::Pipe(Box::new(
+                OpenOptions::new().create(true).append(true).open(path)?,
+            )))
+            .init();
+    }
+    Ok(())
+}
+
+/// Serve MCP on the transport selected by `args`
+pub(crate) fn serve(
+    state: &mut RustdocTools,
+    args: ServeArgs,
+    server_info: Info,
+    instructions: Option<&'static str>,
+) -> Result<()> {
+    if !args.no_watch {
+        // Only stdio has a channel for server-initiated messages
+        let notifier = args
+            .http
+            .is_none()
+            .then(|| -> Notifier { Arc::new(stdio::notify) });
+        state.set_watch(Some(WatchOptions {
+            regenerate: args.regenerate,
+            notifier,
+        }));
+    }
+
+    match args.http {
+        Some(addr) => http::serve(state, addr, &server_info, instructions),
+        None => stdio::serve(state, &server_info, instructions),
+    }
+}
+
+/// Execute a request in the session it belongs to
+///
+/// `transport_session` is the session the transport associates with the connection, if any;
+/// a session id in the request itself takes precedence.
+fn handle_request(
+    state: &mut RustdocTools,
+    request: McpRequest,
+    transport_session: Option<&str>,
+    server_info: &Info,
+    instructions: Option<&'static str>,
+) -> McpResponse {
+    state.set_session_id(
+        request_session_id(&request).or_else(|| transport_session.map(str::to_string)),
+    );
+
+    let response = match request.method.as_str() {
+        "initialize" => McpResponse::success(
+            request.id,
+            InitializeResponse {
+                protocol_version: PROTOCOL_VERSION,
+                capabilities: json!({ "tools": {}, "resources": { "listChanged": true } }),
+                server_info,
+                instructions,
+            },
+        ),
+        "resources/list" => respond(request.id, resources::list(state)),
+        "resources/templates/list" => McpResponse::success(request.id, resources::templates()),
+        "resources/read" => respond(request.id, resources::read(state, request.params)),
+        _ => request.execute::<RustdocTools, Tools>(state, instructions, server_info),
+    };
+
+    state.set_session_id(None);
+    response
+}
+
+fn respond(id: Value, result: Result<impl Serialize>) -> McpResponse {
+    match result {
+        Ok(result) => McpResponse::success(id, result),
+        Err(e) => {
+            log::error!("{e}");
+            McpResponse::error(id, e.to_string())
+        }
+    }
+}
+
+/// The client session a request belongs to, from `params._meta.sessionId`
+///
+/// Clients that share one server between several projects tag each request with a session id
+/// so that each gets its own working directory. Untagged requests use the default session.
+///
+/// Over stdio there is no transport session to go by, so the id travels in the request. It is
+/// not part of the MCP spec, but `_meta` is the place the spec reserves for metadata on any
+/// request: `tools/call` and `resources/read` can both carry it, it stays out of every tool's
+/// input schema, and clients that know nothing of it are unaffected.
+fn request_session_id(request: &McpRequest) -> Option<String> {
+    request
+        .params
+        .as_ref()?
+        .get("_meta")?
+        .get("sessionId")
+        .and_then(Value::as_str)
+        .filter(|session_id| !session_id.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::Path;
+
+    fn send(state: &mut RustdocTools, method: &str, params: Value) -> McpResponse {
+        let request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))
+        .unwrap();
+        handle_request(state, request, None, &mcplease::server_info!(), None)
+    }
+
+    fn call(state: &mut RustdocTools, session_id: &str, name: &str, arguments: Value) -> String {
+        let response = send(
+            state,
+            "tools/call",
+            json!({
+                "name": name,
+                "arguments": arguments,
+                "_meta": { "sessionId": session_id }
+            }),
+        );
+        assert!(response.error.is_none(), "{:?}", response.error);
+        response.result.unwrap()["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn sessions_have_separate_working_directories() {
+        let mut state = RustdocTools::new(None).unwrap();
+        let tests = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+
+        call(
+            &mut state,
+            "a",
+            "set_working_directory",
+            json!({ "path": tests.join("test-crate") }),
+        );
+        call(
+            &mut state,
+            "b",
+            "set_working_directory",
+            json!({ "path": tests.join("test-workspace") }),
+        );
+
+        let a = call(&mut state, "a", "list_crates", json!({}));
+        let b = call(&mut state, "b", "list_crates", json!({}));
+        assert!(a.contains("test-crate"));
+        assert!(!b.contains("test-crate"));
+        assert!(b.contains("crate-a"));
+
+        // both projects stay loaded, and a third session on the same manifest shares one
+        call(
+            &mut state,
+            "c",
+            "set_working_directory",
+            json!({ "path": tests.join("test-crate") }),
+        );
+        call(&mut state, "c", "list_crates", json!({}));
+        assert_eq!(state.projects().len(), 2);
+        assert_eq!(state.session_id(), None);
+    }
+
+    #[test]
+    fn resources() {
+        let mut state = RustdocTools::new(None).unwrap();
+        let test_crate = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/test-crate");
+        call(
+            &mut state,
+            "default",
+            "set_working_directory",
+            json!({ "path": test_crate }),
+        );
+
+        let initialize = send(&mut state, "initialize", json!({})).result.unwrap();
+        assert!(initialize["capabilities"]["resources"].is_object());
+
+        let list = send(&mut state, "resources/list", json!({}))
+            .result
+            .unwrap();
+        assert_eq!(list["resources"][0]["uri"], "rustdoc://test-crate@0.1.0");
+
+        let read = send(
+            &mut state,
+            "resources/read",
+            json!({ "uri": "rustdoc://test-crate@0.1.0/submodule/SubStruct" }),
+        )
+        .result
+        .unwrap();
+        let text = read["contents"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("Item: SubStruct\n"));
+        assert!(text.contains("URI: rustdoc://test-crate@0.1.0/submodule/SubStruct\n"));
+
+        // a re-export cites the item's canonical URI rather than the path it was read by
+        let read = send(
+            &mut state,
+            "resources/read",
+            json!({ "uri": "rustdoc://test-crate@0.1.0/reexport_mod/SubStruct" }),
+        )
+        .result
+        .unwrap();
+        let text = read["contents"][0]["text"].as_str().unwrap();
+        assert!(text.contains("URI: rustdoc://test-crate@0.1.0/submodule/SubStruct\n"));
+
+        let wrong_version = send(
+            &mut state,
+            "resources/read",
+            json!({ "uri": "rustdoc://test-crate@9.9.9/TestStruct" }),
+        );
+        assert!(
+            wrong_version
+                .error
+                .unwrap()
+                .message
+                .contains("this project uses version 0.1.0")
+        );
+
+        let not_found = send(
+            &mut state,
+            "resources/read",
+            json!({ "uri": "rustdoc://test-crate@0.1.0/TestStrukt" }),
+        );
+        assert!(
+            not_found
+                .error
+                .unwrap()
+                .message
+                .contains("• rustdoc://test-crate@0.1.0/TestStruct")
+        );
+
+        // tool output cites URIs that get_item accepts in place of a path
+        let text = call(
+            &mut state,
+            "default",
+            "get_item",
+            json!({ "name": "rustdoc://test-crate@0.1.0/TestStruct" }),
+        );
+        assert!(text.starts_with("Item: TestStruct\n"));
+        assert!(text.contains("URI: rustdoc://test-crate@0.1.0/TestStruct\n"));
+    }
+}
@@ -0,0 +1,723 @@
+use super::handle_request;
+use crate::state::RustdocTools;
+use anyhow::{Result, anyhow, bail};
+use mcplease::types::{Info, McpMessage, McpResponse};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The single endpoint that streamable HTTP clients talk to
+const ENDPOINT: &str = "/mcp";
+
+/// Header carrying the session assigned at initialization
+const SESSION_HEADER: &str = "Mcp-Session-Id";
+
+/// Largest request line plus headers we accept
+const MAX_HEAD_BYTES: u64 = 64 * 1024;
+
+/// Largest request body we accept
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// How long a client has to send its whole request before we give up on its connection
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait on a client that is slow to take its response
+const WRITE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait for a client to close its connection once it has its response
+const LINGER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long a session lasts without requests before the client has to initialize again
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Most connections read or written at once, each on its own thread; more are turned away
+const MAX_CONNECTIONS: usize = 64;
+
+/// A request read from a connection, and where to send its response
+type Exchange = (Result<HttpRequest>, Sender<HttpResponse>);
+
+/// Serve the MCP streamable HTTP transport at `addr`
+///
+/// There is no authentication, and the tools read any path they are pointed at, so only
+/// loopback addresses are accepted.
+pub(super) fn serve(
+    state: &mut RustdocTools,
+    addr: SocketAddr,
+    server_info: &Info,
+    instructions: Option<&'static str>,
+) -> Result<()> {
+    if !addr.ip().is_loopback() {
+        bail!(
+            "refusing to serve http on {addr}: the server has no authentication, so it only \
+             listens on loopback addresses like 127.0.0.1"
+        );
+    }
+    let listener = TcpListener::bind(addr)?;
+    log::info!(
+        "rustdoc-mcp listening on http://{}{ENDPOINT}",
+        listener.local_addr()?
+    );
+    serve_listener(state, listener, server_info, instructions)
+}
+
+/// Handle requests on this thread as connections deliver them
+///
+/// Each connection is read and written on its own thread, so a slow client cannot hold up the
+/// others, while requests are handled one at a time here: the loaded projects are not
+/// thread-safe, and tool calls are short once docs are built. Every response closes its
+/// connection so that an idle client cannot hold a thread.
+fn serve_listener(
+    state: &mut RustdocTools,
+    listener: TcpListener,
+    server_info: &Info,
+    instructions: Option<&'static str>,
+) -> Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || accept(&listener, &sender));
+
+    let mut sessions = Sessions::default();
+    for (request, respond) in receiver {
+        let response = match request {
+            Ok(request) => {
+                log::trace!("<- {} {}", request.method, request.path);
+                handle(state, &mut sessions, request, server_info, instructions)
+            }
+            Err(e) => HttpResponse::json_error(400, "Bad Request", -32700, e.to_string()),
+        };
+        // the connection thread waits for its response, so it is only gone if it panicked
+        let _ = respond.send(response);
+    }
+
+    Ok(())
+}
+
+/// Spawn a thread for each connection that reads its request, passes it to the serving thread
+/// and writes the response
+///
+/// Beyond [`MAX_CONNECTIONS`] open at once, connections are answered with a 503 straight away.
+fn accept(listener: &TcpListener, requests: &Sender<Exchange>) {
+    let open = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("failed to accept connection: {e}");
+                continue;
+            }
+        };
+
+        let Some(slot) = ConnectionSlot::acquire(&open) else {
+            log::warn!("turning away a connection: {MAX_CONNECTIONS} are already open");
+            let _ = HttpResponse::new(503, "Service Unavailable").write_to(&mut stream);
+            continue;
+        };
+
+        let requests = requests.clone();
+        let spawned = thread::Builder::new().spawn(move || {
+            let _slot = slot;
+            let request = HttpRequest::read(&mut stream);
+            let (respond, response) = mpsc::channel();
+            if requests.send((request, respond)).is_err() {
+                return;
+            }
+            if let Ok(response) = response.recv()
+                && let Err(e) = response.write_to(&mut stream)
+            {
+                log::warn!("failed to write response: {e}");
+            }
+            linger(&stream);
+        });
+        if let Err(e) = spawned {
+            log::warn!("failed to spawn connection thread: {e}");
+        }
+    }
+}
+
+/// One of the [`MAX_CONNECTIONS`] connections that may be open at once, given back when its
+/// thread ends
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn acquire(open: &Arc<AtomicUsize>) -> Option<Self> {
+        open.fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+            (count < MAX_CONNECTIONS).then_some(count + 1)
+        })
+        .ok()?;
+        Some(Self(Arc::clone(open)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn handle(
+    state: &mut RustdocTools,
+    sessions: &mut Sessions,
+    request: HttpRequest,
+    server_info: &Info,
+    instructions: Option<&'static str>,
+) -> HttpResponse {
+    let path = request.path.split('?').next().unwrap_or_default();
+    if path != ENDPOINT {
+        return HttpResponse::new(404, "Not Found");
+    }
+
+    // Reject cross-origin requests from web pages, which could otherwise reach a localhost
+    // server through DNS rebinding
+    if let Some(origin) = request.header("Origin")
+        && !is_local_origin(origin)
+    {
+        return HttpResponse::new(403, "Forbidden");
+    }
+
+    for expired in sessions.expire() {
+        state.close_transport_session(&expired);
+    }
+    let session = request.header(SESSION_HEADER);
+
+    match request.method.as_str() {
+        "POST" => {}
+
+        "DELETE" => {
+            return match session {
+                Some(session) if sessions.remove(session) => {
+                    state.close_transport_session(session);
+                    HttpResponse::new(200, "OK")
+                }
+                _ => HttpResponse::new(404, "Not Found"),
+            };
+        }
+
+        // We never send server-initiated messages, so there is no standalone SSE stream
+        _ => {
+            return HttpResponse::new(405, "Method Not Allowed")
+                .with_header("Allow", "POST, DELETE");
+        }
+    }
+
+    let (messages, batch) = match serde_json::from_slice::<Value>(&request.body) {
+        Ok(Value::Array(messages)) => (messages, true),
+        Ok(message) => (vec![message], false),
+        Err(e) => return HttpResponse::json_error(400, "Bad Request", -32700, e.to_string()),
+    };
+
+    let initializing = messages
+        .iter()
+        .any(|message| message.get("method").and_then(Value::as_str) == Some("initialize"));
+
+    let session = if initializing {
+        let session = sessions.create();
+        if let Err(e) = state.open_transport_session(&session) {
+            log::warn!("session {session} starts without a working directory: {e}");
+        }
+        Some(session)
+    } else {
+        match session {
+            Some(session) if sessions.touch(session) => Some(session.to_string()),
+            // Unknown or expired: the client must initialize a new session
+            Some(_) => return HttpResponse::new(404, "Not Found"),
+            None => None,
+        }
+    };
+
+    let mut responses = vec![];
+    for message in messages {
+        match serde_json::from_value(message) {
+            Ok(McpMessage::Request(request)) => responses.push(handle_request(
+                state,
+                request,
+                session.as_deref(),
+                server_info,
+                instructions,
+            )),
+            Ok(McpMessage::Notification(n)) => log::trace!("received {n:?}, ignoring"),
+            Err(e) => log::error!("{e:?}"),
+        }
+    }
+
+    let mut response = if responses.is_empty() {
+        HttpResponse::new(202, "Accepted")
+    } else if request.accepts_only_event_stream() {
+        HttpResponse::event_stream(&responses)
+    } else if batch {
+        HttpResponse::json(&responses)
+    } else {
+        HttpResponse::json(&responses[0])
+    };
+
+    if initializing && let Some(session) = session {
+        response = response.with_header(SESSION_HEADER, &session);
+    }
+
+    response
+}
+
+fn is_local_origin(origin: &str) -> bool {
+    let host = origin
+        .split_once("://")
+        .map_or(origin, |(_, rest)| rest)
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    let host = host
+        .rsplit_once(':')
+        .filter(|(_, port)| port.bytes().all(|b| b.is_ascii_digit()))
+        .map_or(host, |(host, _)| host);
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// Sessions issued by this server, which clients echo in the `Mcp-Session-Id` header
+///
+/// A session that has gone [`SESSION_IDLE_TIMEOUT`] without a request expires, since clients
+/// that exit without a `DELETE` would otherwise accumulate forever. Each session's working
+/// directory is kept in memory by [`RustdocTools::open_transport_session`] until then.
+#[derive(Debug, Default)]
+struct Sessions {
+    last_used: HashMap<String, Instant>,
+    hasher: RandomState,
+}
+
+impl Sessions {
+    fn create(&mut self) -> String {
+        let mut id = String::new();
+        while id.is_empty() || self.last_used.contains_key(&id) {
+            let mut hasher = self.hasher.build_hasher();
+            hasher.write_usize(self.last_used.len());
+            hasher.write_u128(
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos(),
+            );
+            id = format!("{:016x}", hasher.finish());
+        }
+        self.last_used.insert(id.clone(), Instant::now());
+        id
+    }
+
+    /// Whether `id` is a live session, which this request keeps alive
+    fn touch(&mut self, id: &str) -> bool {
+        match self.last_used.get_mut(id) {
+            Some(last_used) => {
+                *last_used = Instant::now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> bool {
+        self.last_used.remove(id).is_some()
+    }
+
+    /// Drop the sessions that have been idle too long, returning their ids
+    fn expire(&mut self) -> Vec<String> {
+        let expired = self
+            .last_used
+            .iter()
+            .filter(|(_, last_used)| last_used.elapsed() >= SESSION_IDLE_TIMEOUT)
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for id in &expired {
+            self.last_used.remove(id);
+        }
+        expired
+    }
+}
+
+/// A connection that fails reads once the deadline for the whole request has passed, rather
+/// than a per-read timeout that a client could reset by trickling bytes
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "request took too long to arrive",
+            ));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+#[derive(Debug)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn read(stream: &mut TcpStream) -> Result<Self> {
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        let mut reader = BufReader::new(Deadline { stream, deadline }).take(MAX_HEAD_BYTES);
+
+        let mut line = String::new();
+        read_head_line(&mut reader, &mut line)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+            bail!("malformed request line");
+        };
+        let (method, path) = (method.to_string(), path.to_string());
+
+        let mut headers = vec![];
+        loop {
+            line.clear();
+            if read_head_line(&mut reader, &mut line)? == 0 {
+                bail!("connection closed before end of headers");
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header
+                .split_once(':')
+                .ok_or_else(|| anyhow!("malformed header `{header}`"))?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+
+        let mut request = Self {
+            method,
+            path,
+            headers,
+            body: vec![],
+        };
+
+        if request.header("Transfer-Encoding").is_some() {
+            bail!("chunked request bodies are not supported; send Content-Length");
+        }
+
+        let length = match request.header("Content-Length") {
+            Some(length) => length.parse::<usize>()?,
+            None => 0,
+        };
+        if length > MAX_BODY_BYTES {
+            bail!("request body of {length} bytes is too large");
+        }
+
+        reader.set_limit(length as u64);
+        request.body = vec![0; length];
+        reader.read_exact(&mut request.body)?;
+        Ok(request)
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the client asked for SSE and cannot take a plain JSON response
+    fn accepts_only_event_stream(&self) -> bool {
+        self.header("Accept").is_some_and(|accept| {
+            accept.contains("text/event-stream") && !accept.contains("application/json")
+        })
+    }
+}
+
+/// Close our side and discard whatever the client still sends, such as the rest of a request
+/// we rejected, since closing with unread data resets the connection before the client has read
+/// our response
+fn linger(stream: &TcpStream) {
+    let _ = stream.shutdown(Shutdown::Write);
+    let deadline = Instant::now() + LINGER_TIMEOUT;
+    let _ = io::copy(
+        &mut Deadline { stream, deadline }.take(MAX_BODY_BYTES as u64),
+        &mut io::sink(),
+    );
+}
+
+/// Read one line of the request head, failing once the head outgrows [`MAX_HEAD_BYTES`]
+fn read_head_line(reader: &mut io::Take<impl BufRead>, line: &mut String) -> Result<usize> {
+    let read = reader.read_line(line)?;
+    if reader.limit() == 0 && !line.ends_with('\n') {
+        bail!("request headers are longer than {MAX_HEAD_BYTES} bytes");
+    }
+    Ok(read)
+}
+
+#[derive(Debug)]
+struct HttpResponse {
+    status: u16,
+    reason: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl HttpResponse {
+    fn new(status: u16, reason: &'static str) -> Self {
+        Self {
+            status,
+            reason,
+            headers: vec![],
+            body: String::new(),
+        }
+    }
+
+    fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    fn json(body: &impl serde::Serialize) -> Self {
+        let mut response = Self::new(200, "OK").with_header("Content-Type", "application/json");
+        response.body = serde_json::to_string(body).unwrap_or_default();
+        response
+    }
+
+    /// A JSON-RPC error that could not be tied to a request id
+    fn json_error(status: u16, reason: &'static str, code: i32, message: String) -> Self {
+        let mut response = Self::json(&json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": code, "message": message },
+        }));
+        response.status = status;
+        response.reason = reason;
+        response
+    }
+
+    /// Send responses as server-sent events, one event per JSON-RPC response
+    fn event_stream(responses: &[McpResponse]) -> Self {
+        let mut response = Self::new(200, "OK").with_header("Content-Type", "text/event-stream");
+        for message in responses {
+            let data = serde_json::to_string(message).unwrap_or_default();
+            response.body.push_str("event: message\ndata: ");
+            response.body.push_str(&data);
+            response.body.push_str("\n\n");
+        }
+        response
+    }
+
+    fn write_to(&self, stream: &mut TcpStream) -> io::Result<()> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            self.reason,
+            self.body.len()
+        );
+        for (name, value) in &self.headers {
+            head.push_str(name);
+            head.push_str(": ");
+            head.push_str(value);
+            head.push_str("\r\n");
+        }
+        head.push_str("\r\n");
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(self.body.as_bytes())?;
+        stream.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// Send one HTTP request and return the status, headers and body of the response
+    fn send(
+        addr: SocketAddr,
+        method: &str,
+        headers: &[(&str, &str)],
+        body: &str,
+    ) -> (u16, String, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let mut request = format!(
+            "{method} {ENDPOINT} HTTP/1.1\r\nHost: {addr}\r\nContent-Length: {}\r\n",
+            body.len()
+        );
+        for (name, value) in headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, head.to_string(), body.to_string())
+    }
+
+    fn rpc(method: &str, params: Value) -> String {
+        json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string()
+    }
+
+    #[test]
+    fn streamable_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let mut state = RustdocTools::new(None).unwrap();
+            serve_listener(&mut state, listener, &mcplease::server_info!(), None)
+        });
+
+        // a client that never sends its request does not hold up the others
+        let _idle = TcpStream::connect(addr).unwrap();
+
+        let accept = ("Accept", "application/json, text/event-stream");
+        let (status, head, body) = send(addr, "POST", &[accept], &rpc("initialize", json!({})));
+        assert_eq!(status, 200);
+        assert!(body.contains("\"serverInfo\""));
+        let session = head
+            .lines()
+            .find_map(|line| line.strip_prefix("Mcp-Session-Id: "))
+            .unwrap()
+            .to_string();
+        let session_header = (SESSION_HEADER, session.as_str());
+
+        let test_crate = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/test-crate");
+        let (status, _, body) = send(
+            addr,
+            "POST",
+            &[accept, session_header],
+            &rpc(
+                "tools/call",
+                json!({
+                    "name": "set_working_directory",
+                    "arguments": { "path": test_crate }
+                }),
+            ),
+        );
+        assert_eq!(status, 200);
+        assert!(body.contains("Set context to"));
+
+        let (status, _, body) = send(
+            addr,
+            "POST",
+            &[("Accept", "text/event-stream"), session_header],
+            &rpc(
+                "tools/call",
+                json!({ "name": "list_crates", "arguments": {} }),
+            ),
+        );
+        assert_eq!(status, 200);
+        assert!(body.starts_with("event: message\ndata: {"));
+        assert!(body.contains("test-crate"));
+
+        // notifications are accepted without a response body
+        let notification =
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }).to_string();
+        let (status, _, body) = send(addr, "POST", &[accept, session_header], &notification);
+        assert_eq!((status, body.as_str()), (202, ""));
+
+        let (status, ..) = send(addr, "GET", &[accept, session_header], "");
+        assert_eq!(status, 405);
+
+        let (status, ..) = send(
+            addr,
+            "POST",
+            &[accept, ("Origin", "http://evil.example")],
+            &rpc("tools/list", json!({})),
+        );
+        assert_eq!(status, 403);
+
+        let long_header = "x".repeat(MAX_HEAD_BYTES as usize);
+        let (status, _, body) = send(
+            addr,
+            "POST",
+            &[accept, ("X-Padding", &long_header)],
+            &rpc("tools/list", json!({})),
+        );
+        assert_eq!(status, 400);
+        assert!(body.contains("request headers are longer than"));
+
+        let (status, ..) = send(addr, "DELETE", &[session_header], "");
+        assert_eq!(status, 200);
+
+        let (status, ..) = send(
+            addr,
+            "POST",
+            &[accept, session_header],
+            &rpc("tools/list", json!({})),
+        );
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn only_loopback_addresses() {
+        let mut state = RustdocTools::new(None).unwrap();
+        let error = serve(
+            &mut state,
+            "0.0.0.0:0".parse().unwrap(),
+            &mcplease::server_info!(),
+            None,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("only listens on loopback"));
+    }
+
+    #[test]
+    fn idle_sessions_expire() {
+        let mut sessions = Sessions::default();
+        let session = sessions.create();
+        assert!(sessions.touch(&session));
+
+        *sessions.last_used.get_mut(&session).unwrap() -= SESSION_IDLE_TIMEOUT;
+        assert_eq!(sessions.expire(), std::slice::from_ref(&session));
+        assert!(!sessions.touch(&session));
+        assert!(!sessions.remove(&session));
+    }
+
+    #[test]
+    fn session_directories_are_not_stored() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = dir.path().join("sessions.json");
+        let mut state = RustdocTools::new(Some(store.clone())).unwrap();
+        let test_crate = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/test-crate");
+        state
+            .set_working_directory(test_crate.clone(), None)
+            .unwrap();
+
+        // a new session starts where the default session is
+        state.open_transport_session("issued").unwrap();
+        assert_eq!(
+            state.get_context(Some("issued")).unwrap(),
+            Some(test_crate.clone())
+        );
+        state
+            .set_working_directory(dir.path().to_path_buf(), Some("issued"))
+            .unwrap();
+        assert_eq!(
+            state.get_context(Some("issued")).unwrap(),
+            Some(dir.path().to_path_buf())
+        );
+        assert!(!std::fs::read_to_string(&store).unwrap().contains("issued"));
+
+        state.close_transport_session("issued");
+        assert_eq!(state.get_context(Some("issued")).unwrap(), None);
+    }
+
+    #[test]
+    fn connections_are_capped() {
+        let open = Arc::new(AtomicUsize::new(0));
+        let slots = (0..MAX_CONNECTIONS)
+            .map(|_| ConnectionSlot::acquire(&open).unwrap())
+            .collect::<Vec<_>>();
+        assert!(ConnectionSlot::acquire(&open).is_none());
+        drop(slots);
+        assert!(ConnectionSlot::acquire(&open).is_some());
+    }
+}
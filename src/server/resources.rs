@@ -0,0 +1,135 @@
+use crate::format_context::FormatContext;
+use crate::request::Request;
+use crate::resource_uri::ResourceUri;
+use crate::state::RustdocTools;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const MIME_TYPE: &str = "text/plain";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ListResourcesResponse {
+    resources: Vec<Resource>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Resource {
+    uri: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    mime_type: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ListResourceTemplatesResponse {
+    resource_templates: Vec<ResourceTemplate>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceTemplate {
+    uri_template: &'static str,
+    name: &'static str,
+    description: &'static str,
+    mime_type: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReadResourceParams {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ReadResourceResponse {
+    contents: Vec<ResourceContents>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceContents {
+    uri: String,
+    mime_type: &'static str,
+    text: String,
+}
+
+/// List the crates of the current workspace as resources
+pub(super) fn list(state: &mut RustdocTools) -> Result<ListResourcesResponse> {
+    let project = state.project_context(None)?;
+    let resources = project
+        .crate_info(None)
+        .filter(|info| info.crate_type().is_workspace())
+        .map(|info| Resource {
+            uri: ResourceUri::new(info.name(), info.version(), vec![]).to_string(),
+            name: info.name().to_string(),
+            description: info.description().map(str::to_string),
+            mime_type: MIME_TYPE,
+        })
+        .collect();
+
+    Ok(ListResourcesResponse { resources })
+}
+
+/// Describe the URI scheme, so clients can address any item rather than only listed crates
+pub(super) fn templates() -> ListResourceTemplatesResponse {
+    ListResourceTemplatesResponse {
+        resource_templates: vec![ResourceTemplate {
+            uri_template: ResourceUri::TEMPLATE,
+            name: "Rust item documentation",
+            description: "Documentation for a crate, module or item, with `/`-separated path \
+                          segments (e.g. rustdoc://serde_json@1.0.143/value/Value)",
+            mime_type: MIME_TYPE,
+        }],
+    }
+}
+
+/// Render the item a URI refers to, as `get_item` would
+pub(super) fn read(
+    state: &mut RustdocTools,
+    params: Option<Value>,
+) -> Result<ReadResourceResponse> {
+    let ReadResourceParams { uri } = serde_json::from_value(params.unwrap_or_default())?;
+    let resource_uri = ResourceUri::parse(&uri)?;
+    let path = resource_uri.item_path();
+
+    let request = Request::new(state.project_context(None)?);
+    let mut suggestions = vec![];
+    let Some(item) = request.resolve_uri(&resource_uri, &mut suggestions)? else {
+        if let Some(error) = request.load_error(&path) {
+            return Err(error.clone().into());
+        }
+
+        request.suggest_across_crates(&path, &mut suggestions);
+        suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
+        let alternatives = suggestions
+            .iter()
+            .take(5)
+            .filter(|s| s.score() > 0.8)
+            .filter_map(|s| request.resource_uri(s.path()))
+            .map(|uri| format!("\n• {uri}"))
+            .collect::<String>();
+
+        return Err(if alternatives.is_empty() {
+            anyhow!("`{uri}` not found")
+        } else {
+            anyhow!("`{uri}` not found. Did you mean one of these?\n{alternatives}")
+        });
+    };
+
+    let context =
+        FormatContext::default().with_uri(request.item_uri(item, &path).map(|uri| uri.to_string()));
+    let text = request.format_item(item, &context);
+
+    Ok(ReadResourceResponse {
+        contents: vec![ResourceContents {
+            uri,
+            mime_type: MIME_TYPE,
+            text,
+        }],
+    })
+}
@@ -0,0 +1,74 @@
+//! The stdio transport
+//!
+//! This is a fork of the loop that `mcplease::run` serves stdio with (the private `serve` in
+//! mcplease 0.2.3), which offers no hook around each request. The fork exists for three things:
+//!
+//! - dispatching `resources/*` methods, which `McpRequest::execute` does not know
+//! - setting the session a request belongs to for the duration of the request
+//! - holding stdout for a whole line, since the watcher writes notifications to it as well
+//!
+//! Everything else should stay as upstream has it. Once mcplease can take a request handler,
+//! this module can go in favor of passing it [`handle_request`].
+
+use super::handle_request;
+use crate::state::RustdocTools;
+use anyhow::Result;
+use mcplease::types::{Info, McpMessage};
+use serde_json::json;
+use std::io::{BufRead, BufReader, Write};
+
+/// Serve MCP over stdio, one JSON-RPC message per line
+pub(super) fn serve(
+    state: &mut RustdocTools,
+    server_info: &Info,
+    instructions: Option<&'static str>,
+) -> Result<()> {
+    let mut reader = BufReader::new(std::io::stdin());
+    let mut line = String::new();
+
+    log::trace!("started!");
+
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break, // EOF
+            Ok(_) => {
+                log::trace!("<- {line}");
+                match serde_json::from_str(&line) {
+                    Ok(McpMessage::Request(request)) => {
+                        let response =
+                            handle_request(state, request, None, server_info, instructions);
+                        let response_str = serde_json::to_string(&response)?;
+                        log::trace!("-> {response_str}");
+                        // hold the lock for the whole line, as the watcher may also write
+                        let mut stdout = std::io::stdout().lock();
+                        stdout.write_all(response_str.as_bytes())?;
+                        stdout.write_all(b"\n")?;
+                        stdout.flush()?;
+                    }
+                    Ok(McpMessage::Notification(n)) => {
+                        log::trace!("received {n:?}, ignoring");
+                    }
+                    Err(e) => {
+                        log::error!("{e:?}");
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("Error reading line: {e}");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Send a notification to the stdio client
+pub(super) fn notify(method: &str) {
+    let notification = json!({ "jsonrpc": "2.0", "method": method });
+    log::trace!("-> {notification}");
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{notification}");
+    let _ = stdout.flush();
+}
@@ -1,11 +1,15 @@
-use crate::rustdoc::RustdocProject;
+use crate::rustdoc::{RustdocProject, WatchOptions};
 use anyhow::{Result, anyhow};
 use fieldwork::Fieldwork;
 use mcplease::session::SessionStore;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::{fmt::Debug, fs, path::PathBuf};
 
+/// How many loaded projects to keep in memory across all sessions
+const MAX_CACHED_PROJECTS: usize = 4;
+
 /// Shared context data that can be used across multiple MCP servers
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq)]
 pub(crate) struct SharedContextData {
@@ -20,11 +24,26 @@ pub(crate) struct RustdocTools {
     /// Shared context store for cross-server communication (working directory)
     shared_context_store: SessionStore<SharedContextData>,
 
+    /// Session used when a request does not identify one
     #[field(set, with)]
     default_session_id: &'static str,
 
-    /// Cached rustdoc project (lazily loaded)
-    cached_project: Option<Rc<RustdocProject>>,
+    /// Session of the request currently being handled, as identified by the client
+    #[field(set, with)]
+    session_id: Option<String>,
+
+    /// Working directories of the sessions a transport issued, like streamable HTTP's, which
+    /// end with their connection's session and so are kept out of the shared store
+    #[field = false]
+    transport_sessions: HashMap<String, Option<PathBuf>>,
+
+    /// Whether and how to watch loaded projects for changes, which only `serve` sets
+    #[field(set, with)]
+    watch: Option<WatchOptions>,
+
+    /// Loaded rustdoc projects keyed by manifest path, most recently used first.
+    /// Sessions pointing at the same manifest share an entry.
+    projects: Vec<Rc<RustdocProject>>,
 }
 
 impl RustdocTools {
@@ -35,29 +54,60 @@ impl RustdocTools {
         Ok(Self {
             shared_context_store,
             default_session_id: "default",
-            cached_project: None,
+            session_id: None,
+            transport_sessions: HashMap::new(),
+            watch: None,
+            projects: Vec::new(),
         })
     }
 
-    /// Get context (working directory) for a session
+    /// The session to use when none is passed explicitly: the current request's, or the default
+    fn current_session_id<'a>(&'a self, session_id: Option<&'a str>) -> &'a str {
+        session_id
+            .or(self.session_id.as_deref())
+            .unwrap_or(self.default_session_id)
+    }
+
+    /// Start a session that a transport issued, in the default session's working directory
+    pub(crate) fn open_transport_session(&mut self, session_id: &str) -> Result<()> {
+        let context_path = self
+            .shared_context_store
+            .get(self.default_session_id)?
+            .and_then(|data| data.context_path.clone());
+        self.transport_sessions
+            .insert(session_id.to_string(), context_path);
+        Ok(())
+    }
+
+    /// Forget a session that a transport issued, once it is deleted or expires
+    pub(crate) fn close_transport_session(&mut self, session_id: &str) {
+        self.transport_sessions.remove(session_id);
+    }
+
+    /// Get context (working directory) for a session, defaulting to the current request's session
     pub(crate) fn get_context(&mut self, session_id: Option<&str>) -> Result<Option<PathBuf>> {
-        let session_id = session_id.unwrap_or_else(|| self.default_session_id());
-        let shared_data = self.shared_context_store.get_or_create(session_id)?;
+        let session_id = self.current_session_id(session_id).to_string();
+        if let Some(context_path) = self.transport_sessions.get(&session_id) {
+            return Ok(context_path.clone());
+        }
+        let shared_data = self.shared_context_store.get_or_create(&session_id)?;
         Ok(shared_data.context_path.clone())
     }
 
-    /// Set working directory for a session (shared across MCP servers)
+    /// Set working directory for a session (shared across MCP servers, unless a transport
+    /// issued the session)
     pub(crate) fn set_working_directory(
         &mut self,
         path: PathBuf,
         session_id: Option<&str>,
     ) -> Result<()> {
-        let session_id = session_id.unwrap_or_else(|| self.default_session_id());
-
-        // Clear cached project when working directory changes
-        self.cached_project = None;
+        let session_id = self.current_session_id(session_id).to_string();
+        if let Some(context_path) = self.transport_sessions.get_mut(&session_id) {
+            *context_path = Some(path);
+            return Ok(());
+        }
 
-        self.shared_context_store.update(session_id, |data| {
+        self.shared_context_store.update(&session_id, |data| {
             data.context_path = Some(path);
         })
     }
@@ -70,17 +120,37 @@ impl RustdocTools {
         let manifest_path = self.resolve_path("Cargo.toml", session_id)?;
         log::trace!("using manifest path {}", manifest_path.display());
 
-        // If we already have a cached project, return it
-        if let Some(project) = self.cached_project.clone()
-            && project.manifest_path() == &*manifest_path
+        // If we already have this project loaded, mark it most recently used and return it
+        if let Some(index) = self
+            .projects
+            .iter()
+            .position(|project| project.manifest_path() == &*manifest_path)
         {
-            return Ok(project);
-        };
+            let project = self.projects.remove(index);
+            if !project.is_stale() {
+                self.projects.insert(0, Rc::clone(&project));
+                return Ok(project);
+            }
+            log::debug!(
+                "reloading {} after manifest change",
+                manifest_path.display()
+            );
+        }
 
-        // Load the project
-        let project = Rc::new(RustdocProject::load(manifest_path)?);
+        // Load the project, evicting the least recently used one if we're at capacity
+        let mut project = RustdocProject::load(manifest_path)?;
+        if let Some(options) = &self.watch
+            && let Err(error) = project.watch(options.clone())
+        {
+            log::warn!(
+                "not watching {}: {error}",
+                project.manifest_path().display()
+            );
+        }
+        let project = Rc::new(project);
 
-        self.cached_project = Some(Rc::clone(&project));
+        self.projects.insert(0, Rc::clone(&project));
+        self.projects.truncate(MAX_CACHED_PROJECTS);
 
         Ok(project)
     }
@@ -96,8 +166,7 @@ impl RustdocTools {
             return fs::canonicalize(path).map_err(Into::into);
         }
 
-        let session_id = session_id.unwrap_or_else(|| self.default_session_id());
-        match self.get_context(Some(session_id))? {
+        match self.get_context(session_id)? {
             Some(context) => fs::canonicalize(context.join(path_str)).map_err(Into::into),
             None => Err(anyhow!(
                 "Use set_working_directory first or provide an absolute path.",
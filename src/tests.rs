@@ -1,7 +1,8 @@
 use crate::{
     filter::Filter,
+    output_format::OutputFormat,
     state::RustdocTools,
-    tools::{GetItem, ListCrates, Search, SetWorkingDirectory},
+    tools::{GetItem, Implements, ListCrates, ListMethods, Search, SetWorkingDirectory},
     verbosity::Verbosity,
 };
 use mcplease::traits::Tool;
@@ -126,7 +127,7 @@ fn test_fuzzy_matching_trait_methods() {
     // Try to access a trait method that should be available via impl
     // This tests whether we collect trait implementation methods
     let tool = GetItem {
-        name: "crate::TestStruct::cute".to_string(), // Should suggest "clone" from Clone trait
+        name: "crate::TestStruct::clne".to_string(), // Should suggest "clone" from Clone trait
         ..Default::default()
     };
 
@@ -135,7 +136,17 @@ fn test_fuzzy_matching_trait_methods() {
     // Should contain suggestions from trait implementations
     assert!(result.contains("Did you mean"));
     // Should suggest trait methods that are actually available
-    // TestStruct implements Clone, so "clone" should be suggested for "cute"
+    // TestStruct implements Clone, so "clone" should be suggested for "clne"
+    assert!(result.contains("`crate::TestStruct::clone`"));
+
+    // but not for a name that is nowhere near them
+    let tool = GetItem {
+        name: "crate::TestStruct::cute".to_string(),
+        ..Default::default()
+    };
+    let result = tool.execute(&mut state).expect("Tool execution failed");
+    assert!(!result.contains("`crate::TestStruct::clone`"));
+    assert!(!result.contains("`crate::TestStruct::fmt`"));
 
     insta::assert_snapshot!(result);
 }
@@ -499,6 +510,40 @@ fn test_get_std_vec() {
         .expect("Tool execution failed for std::vec::Vec");
     insta::assert_snapshot!(result_std_vec_vec);
 }
+
+#[test]
+fn test_get_primitives() {
+    let mut state = create_test_state();
+    let mut get = |name: &str| {
+        GetItem {
+            name: name.to_string(),
+            ..Default::default()
+        }
+        .execute(&mut state)
+        .unwrap_or_else(|e| panic!("Tool execution failed for {name}: {e}"))
+    };
+
+    // a primitive is found by its bare name, and lists its inherent methods
+    let str_docs = get("str");
+    assert!(
+        str_docs.starts_with("Item: str\nKind: Primitive\n"),
+        "{str_docs}"
+    );
+    assert!(str_docs.contains("• pub fn split_once<P>"), "{str_docs}");
+
+    // most methods of a primitive are in core's impls rather than std's
+    let split_once = get("str::split_once");
+    assert!(
+        split_once.contains("fn split_once<P>(&self, delimiter: P) -> Option<(&str, &str)>"),
+        "{split_once}"
+    );
+
+    let checked_add = get("u64::checked_add");
+    assert!(
+        checked_add.contains("const fn checked_add(self, rhs: u64) -> Option<u64>"),
+        "{checked_add}"
+    );
+}
 #[test]
 fn test_get_item_with_normalized_crate_name() {
     let mut state = create_test_state();
@@ -546,9 +591,10 @@ fn list_crates() {
 fn search() {
     let mut state = create_test_state();
     let result = Search {
-        crate_name: "crate".into(),
+        crate_name: Some("crate".into()),
         query: "trigger line-based truncation".into(),
         limit: None,
+        ..Default::default()
     }
     .execute(&mut state)
     .unwrap();
@@ -559,11 +605,964 @@ fn search() {
 fn search_2() {
     let mut state = create_test_state();
     let result = Search {
-        crate_name: "crate".into(),
+        crate_name: Some("crate".into()),
         query: "generic struct".into(),
         limit: None,
+        ..Default::default()
     }
     .execute(&mut state)
     .unwrap();
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn doc_build_failure_is_reported() {
+    let mut state = RustdocTools::new(None)
+        .expect("Failed to create state")
+        .with_default_session_id("broken");
+
+    SetWorkingDirectory {
+        path: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/broken-crate")
+            .to_string_lossy()
+            .to_string(),
+    }
+    .execute(&mut state)
+    .unwrap();
+
+    let error = GetItem {
+        name: "crate::broken".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .expect_err("a crate that does not compile should be an error, not a suggestion list")
+    .to_string();
+
+    assert!(error.contains("Failed to build docs for `broken-crate`"));
+    assert!(error.contains("Likely cause: the crate does not compile"));
+    assert!(error.contains("Command: RUSTDOCFLAGS="));
+    assert!(error.contains("cannot find type `DoesNotExist`"));
+}
+
+#[test]
+fn get_item_json_output() {
+    let mut state = create_test_state();
+
+    let result = GetItem {
+        name: "crate::TestStruct".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(json["name"], "TestStruct");
+    assert_eq!(json["kind"], "struct");
+    assert_eq!(json["path"], "test_crate::TestStruct");
+    assert_eq!(json["signature"], "struct TestStruct");
+    assert_eq!(json["fields"][0]["name"], "field");
+    assert_eq!(json["fields"][0]["type"], "String");
+    assert_eq!(json["fields"][1]["docs"], "Another public field");
+    assert!(
+        json["methods"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|method| method["signature"] == "fn new(field: String, count: u32) -> Self")
+    );
+    assert!(
+        json["trait_impls"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|t| t["trait"] == "TestTrait" && t["path"] == "test_crate::TestTrait")
+    );
+    assert_eq!(json["span"]["filename"], "src/lib.rs");
+}
+
+#[test]
+fn get_item_json_not_found() {
+    let mut state = create_test_state();
+
+    let result = GetItem {
+        name: "crate::TestStruct::incrementCount".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(json["not_found"], "crate::TestStruct::incrementCount");
+    assert_eq!(
+        json["suggestions"][0]["path"],
+        "crate::TestStruct::increment_count"
+    );
+    assert_eq!(json["suggestions"][0]["kind"], "function");
+}
+
+#[test]
+fn not_found_suggests_items_elsewhere_in_the_crate() {
+    let mut state = create_test_state();
+    let mut suggested_paths = |name: &str| {
+        let result = GetItem {
+            name: name.to_string(),
+            output: Some(OutputFormat::Json),
+            ..Default::default()
+        }
+        .execute(&mut state)
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+        json["suggestions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|suggestion| suggestion["path"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // not a sibling of the module the lookup failed in
+    assert_eq!(
+        suggested_paths("crate::reexport_mod::test_functon")[0],
+        "crate::test_function"
+    );
+
+    // paths a re-exported item is reachable at, keeping the segments after the typo
+    let paths = suggested_paths("crate::reexport_mod::SubStrct::new");
+    for path in [
+        "crate::submodule::SubStruct::new",
+        "crate::reexport_mod::SubStruct::new",
+    ] {
+        assert!(paths.iter().any(|p| p == path), "{path} not in {paths:?}");
+    }
+
+    // without a crate, workspace crates are searched
+    assert!(
+        suggested_paths("SubStruct")
+            .iter()
+            .any(|path| path == "test-crate::submodule::SubStruct")
+    );
+}
+
+#[test]
+fn search_and_list_crates_json_output() {
+    let mut state = create_test_state();
+
+    let result = Search {
+        crate_name: Some("crate".into()),
+        query: "generic struct".into(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(json["crate"], "test-crate");
+    assert_eq!(json["results"][0]["path"], "test-crate::GenericStruct");
+    assert_eq!(json["results"][0]["kind"], "struct");
+
+    let result = ListCrates {
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(json[0]["name"], "test-crate");
+    assert_eq!(json[0]["crate_type"], "workspace");
+    assert_eq!(json[0]["default_crate"], true);
+}
+
+/// Extract the continuation cursor from a truncated text response
+fn next_cursor(result: &str) -> String {
+    let (_, rest) = result
+        .split_once("cursor: \"")
+        .expect("response should include a cursor");
+    rest.split('"').next().unwrap().to_string()
+}
+
+#[test]
+fn module_listing_pagination() {
+    let mut state = create_test_state();
+    let listing = |result: &str| {
+        result
+            .lines()
+            .filter(|line| {
+                !line.is_empty()
+                    && !line.ends_with(':')
+                    && !line.contains(": ")
+                    && !line.starts_with('[')
+            })
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    let full = GetItem {
+        name: "crate".to_string(),
+        recursive: Some(true),
+        verbosity: Some(Verbosity::Minimal),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let full = listing(&full);
+
+    let first = GetItem {
+        name: "crate".to_string(),
+        recursive: Some(true),
+        verbosity: Some(Verbosity::Minimal),
+        max_items: Some(5),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert_eq!(listing(&first), full[..5]);
+    assert!(first.contains(&format!("[Showing items 1-5 of {}; ", full.len())));
+    assert!(first.contains("Functions: "));
+
+    let cursor = next_cursor(&first);
+    let second = GetItem {
+        name: "crate".to_string(),
+        recursive: Some(true),
+        verbosity: Some(Verbosity::Minimal),
+        max_items: Some(5),
+        cursor: Some(cursor.clone()),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert_eq!(listing(&second), full[5..10]);
+    assert!(second.contains(&format!("[Showing items 6-10 of {}; ", full.len())));
+
+    let error = GetItem {
+        name: "crate::submodule".to_string(),
+        recursive: Some(true),
+        verbosity: Some(Verbosity::Minimal),
+        max_items: Some(5),
+        cursor: Some(cursor),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap_err();
+    assert!(error.to_string().contains("belongs to a different request"));
+}
+
+#[test]
+fn item_text_truncation() {
+    let mut state = create_test_state();
+
+    let first = GetItem {
+        name: "crate::TestStruct".to_string(),
+        max_chars: Some(200),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(first.starts_with("Item: TestStruct\n"));
+    assert!(first.contains("[Output truncated: showing characters 1-"));
+
+    let second = GetItem {
+        name: "crate::TestStruct".to_string(),
+        max_chars: Some(200),
+        cursor: Some(next_cursor(&first)),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(!second.starts_with("Item: TestStruct\n"));
+}
+
+#[test]
+fn search_pagination() {
+    let mut state = create_test_state();
+
+    let first = Search {
+        crate_name: Some("crate".into()),
+        query: "struct".into(),
+        max_items: Some(1),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert_eq!(first.matches("• ").count(), 1);
+    assert!(first.contains("[Showing results 1-1 of "));
+
+    let second = Search {
+        crate_name: Some("crate".into()),
+        query: "struct".into(),
+        max_items: Some(1),
+        cursor: Some(next_cursor(&first)),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert_eq!(second.matches("• ").count(), 1);
+    assert_ne!(
+        first.lines().find(|line| line.starts_with("• ")),
+        second.lines().find(|line| line.starts_with("• "))
+    );
+}
+
+#[test]
+fn manifest_changes_reload_project() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+    let write_manifest = |description: &str| {
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"reloaded\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\
+                 description = \"{description}\"\n"
+            ),
+        )
+        .unwrap();
+    };
+    write_manifest("before the edit");
+
+    let mut state = RustdocTools::new(None)
+        .expect("Failed to create state")
+        .with_default_session_id("reload");
+    SetWorkingDirectory {
+        path: dir.path().to_string_lossy().to_string(),
+    }
+    .execute(&mut state)
+    .unwrap();
+
+    let result = ListCrates::default().execute(&mut state).unwrap();
+    assert!(result.contains("before the edit"));
+
+    let project = state.project_context(None).unwrap();
+    assert!(std::rc::Rc::ptr_eq(
+        &project,
+        &state.project_context(None).unwrap()
+    ));
+
+    write_manifest("after the edit");
+    let result = ListCrates::default().execute(&mut state).unwrap();
+    assert!(result.contains("after the edit"));
+    assert!(!result.contains("before the edit"));
+}
+
+#[test]
+fn search_everything_skips_unbuilt_docs() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"unbuilt\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\ntest-crate = {{ path = {:?} }}\n",
+            get_test_crate_path()
+        ),
+    )
+    .unwrap();
+
+    let mut state = RustdocTools::new(None)
+        .expect("Failed to create state")
+        .with_default_session_id("unbuilt");
+    SetWorkingDirectory {
+        path: dir.path().to_string_lossy().to_string(),
+    }
+    .execute(&mut state)
+    .unwrap();
+
+    let result = Search {
+        query: "struct".into(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        result.contains(
+            "Not searched, as their docs are not built or could not be loaded: unbuilt, test-crate"
+        ),
+        "{result}"
+    );
+    assert!(result.contains("passing it as `crate_name`"), "{result}");
+    assert!(!dir.path().join("target/doc").exists(), "{result}");
+}
+
+#[test]
+fn search_filters() {
+    let mut state = create_test_state();
+    let search = |search: Search, state: &mut RustdocTools| search.execute(state).unwrap();
+
+    // without a filter, the struct outranks and hides the function
+    let result = search(
+        Search {
+            crate_name: Some("crate".into()),
+            query: "generic".into(),
+            filter: Some(vec![Filter::Function]),
+            ..Default::default()
+        },
+        &mut state,
+    );
+    assert!(result.contains("• test-crate::generic_function (Function)"));
+    assert!(!result.contains("(Struct)"));
+
+    // a path prefix picks the crate to search
+    let result = search(
+        Search {
+            query: "struct".into(),
+            path_prefix: Some("crate::submodule".into()),
+            ..Default::default()
+        },
+        &mut state,
+    );
+    assert!(result.starts_with("Search results for 'struct' in crate 'test-crate'"));
+    assert!(result.contains("• test-crate::submodule::SubStruct (Struct)"));
+    assert_eq!(
+        result.matches("• ").count(),
+        result.matches("• test-crate::submodule::").count()
+    );
+
+    // `pub use std::vec::Vec` is a re-export from another crate
+    let result = search(
+        Search {
+            crate_name: Some("crate".into()),
+            query: "Vec".into(),
+            exclude_reexports: Some(true),
+            ..Default::default()
+        },
+        &mut state,
+    );
+    assert!(!result.contains("test-crate::Vec "));
+
+    let error = Search {
+        crate_name: Some("crate".into()),
+        query: "Vec".into(),
+        path_prefix: Some("std::vec".into()),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap_err();
+    assert!(error.to_string().contains("must be within `crate`"));
+}
+
+/// Regression tests for ranking with the default field weights
+#[test]
+fn search_ranking() {
+    let mut state = create_test_state();
+    let mut top_results = |query: &str| {
+        let result = Search {
+            crate_name: Some("crate".into()),
+            query: query.into(),
+            output: Some(OutputFormat::Json),
+            ..Default::default()
+        }
+        .execute(&mut state)
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+        json["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| result["path"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // a short item named for the query beats one whose long docs repeat it
+    assert_eq!(top_results("generic")[0], "test-crate::generic_function");
+    // names in signatures are indexed, with `str` and `String` as one term
+    assert_eq!(
+        top_results("str")[..2],
+        [
+            "test-crate::test_function",
+            "test-crate::submodule::sub_function"
+        ]
+    );
+    // the module comes first, then what is in it
+    assert_eq!(
+        top_results("submodule")[..2],
+        ["test-crate::submodule", "test-crate::submodule::SubStruct"]
+    );
+    assert_eq!(
+        top_results("value")[0],
+        "test-crate::submodule::SubStruct::get_value"
+    );
+}
+
+#[test]
+fn resolves_members_and_qualified_paths() {
+    let mut state = create_test_state();
+    let mut get_json = |name: &str| {
+        let result = GetItem {
+            name: name.to_string(),
+            output: Some(OutputFormat::Json),
+            ..Default::default()
+        }
+        .execute(&mut state)
+        .unwrap();
+        serde_json::from_str::<serde_json::Value>(&result).unwrap()
+    };
+
+    for (path, kind, signature) in [
+        (
+            "crate::TestTrait::test_method",
+            "function",
+            "fn test_method(&self) -> String",
+        ),
+        ("crate::TestStruct::field", "struct_field", "field: String"),
+        ("crate::TupleStruct::0", "struct_field", "0: String"),
+        (
+            "crate::submodule::TestEnum::VariantC",
+            "variant",
+            "VariantC { name: String, value: i32 }",
+        ),
+        (
+            "crate::submodule::TestEnum::VariantB",
+            "variant",
+            "VariantB(String)",
+        ),
+        (
+            "crate::submodule::TestEnum::VariantC::value",
+            "struct_field",
+            "value: i32",
+        ),
+        ("crate::TestStruct::T", "assoc_type", "type T = String"),
+        (
+            "<crate::TestStruct as TestTrait>::process",
+            "function",
+            "fn process(&mut self, data: &str) -> Result<(), String>",
+        ),
+        (
+            "<crate::TestStruct as crate::TestTrait>::ASSOCIATED_CONSTANT",
+            "assoc_const",
+            "const ASSOCIATED_CONSTANT: () = ()",
+        ),
+        (
+            "<crate::TestStruct>::new",
+            "function",
+            "fn new(field: String, count: u32) -> Self",
+        ),
+    ] {
+        let json = get_json(path);
+        assert_eq!(json["kind"], kind, "{path}: {json}");
+        assert_eq!(json["signature"], signature, "{path}: {json}");
+    }
+
+    assert_eq!(
+        get_json("crate::submodule::TestEnum::VariantC")["fields"][1]["name"],
+        "value"
+    );
+    assert_eq!(
+        get_json("<crate::TestStruct as TestTrait>::proces")["suggestions"][0]["path"],
+        "<crate::TestStruct as TestTrait>::process"
+    );
+
+    let text = GetItem {
+        name: "crate::TestStruct::count".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(text.contains("```rust\ncount: u32\n```"), "{text}");
+}
+
+#[test]
+fn trait_impl_details_and_single_impls() {
+    let mut state = create_test_state();
+
+    let result = GetItem {
+        name: "crate::TestStruct".to_string(),
+        impl_details: Some(true),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        result.contains(
+            "impl TestTrait for TestStruct {\n    const ASSOCIATED_CONSTANT: () = ();\n    type T = String;\n}"
+        ),
+        "{result}"
+    );
+    assert!(
+        result.contains("```rust\nimpl Clone for TestStruct\n```"),
+        "{result}"
+    );
+
+    let json = GetItem {
+        name: "crate::TestStruct".to_string(),
+        impl_details: Some(true),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let test_trait = json["trait_impls"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|trait_impl| trait_impl["trait"] == "TestTrait")
+        .unwrap();
+    assert_eq!(test_trait["header"], "impl TestTrait for TestStruct");
+    assert_eq!(test_trait["bindings"][1], "type T = String");
+
+    let result = GetItem {
+        name: "crate::TestStruct as TestTrait".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(result.contains("Kind: Impl"), "{result}");
+    assert!(!result.contains("URI:"), "{result}");
+    assert!(
+        result.contains("```rust\nimpl TestTrait for TestStruct\n```"),
+        "{result}"
+    );
+    assert!(
+        result.contains("fn process(&mut self, data: &str) -> Result<(), String>"),
+        "{result}"
+    );
+
+    let result = GetItem {
+        name: "crate::TestStruct as TestTrai".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        result.contains("`crate::TestStruct as TestTrait` (Impl)"),
+        "{result}"
+    );
+
+    // generic arguments pick one impl of a trait, and without them every impl is suggested
+    let result = GetItem {
+        name: "u32 as From<u8>".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        result.contains("```rust\nimpl From<u8> for u32\n```"),
+        "{result}"
+    );
+
+    let result = GetItem {
+        name: "u32 as From".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(result.starts_with("`u32 as From` not found"), "{result}");
+    assert!(result.matches("• `u32 as From<").count() > 1, "{result}");
+}
+
+#[test]
+fn auto_traits_and_blanket_impls_are_set_apart() {
+    let mut state = create_test_state();
+    let result = GetItem {
+        name: "crate::TestStruct".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let category = |trait_: &str| {
+        json["trait_impls"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|trait_impl| trait_impl["trait"] == trait_)
+            .map(|trait_impl| trait_impl["category"].clone())
+            .unwrap_or_else(|| panic!("{trait_} not in {json}"))
+    };
+    assert_eq!(category("Send"), "auto");
+    assert_eq!(category("Unpin"), "auto");
+    assert_eq!(category("Into<U>"), "blanket");
+    assert_eq!(category("Clone"), "std");
+}
+
+#[test]
+fn implements_follows_impl_bounds() {
+    let mut state = create_test_state();
+    let mut check = |type_name: &str, trait_name: &str| {
+        let result = Implements {
+            type_name: type_name.to_string(),
+            trait_name: trait_name.to_string(),
+            output: Some(OutputFormat::Json),
+        }
+        .execute(&mut state)
+        .unwrap();
+        serde_json::from_str::<serde_json::Value>(&result).unwrap()
+    };
+
+    assert_eq!(
+        check("crate::TestStruct", "Clone")["verdict"],
+        "implemented"
+    );
+    assert_eq!(check("crate::TestStruct", "Send")["verdict"], "implemented");
+    assert_eq!(
+        check("crate::TestStruct", "Copy")["verdict"],
+        "not_implemented"
+    );
+
+    // traits are matched by definition and arguments, not by name
+    assert_eq!(check("u32", "From<u8>")["verdict"], "implemented");
+    assert_eq!(check("u32", "From<String>")["verdict"], "not_implemented");
+    assert_eq!(check("String", "std::fmt::Write")["verdict"], "implemented");
+    let io_write = check("String", "std::io::Write");
+    assert_eq!(io_write["verdict"], "unknown", "{io_write}");
+
+    // and so are the types in a trait's arguments
+    assert_eq!(
+        check("crate::UnitStruct", "From<std::io::Error>")["verdict"],
+        "implemented"
+    );
+    let fmt_error = check("crate::UnitStruct", "From<std::fmt::Error>");
+    assert_eq!(fmt_error["verdict"], "not_implemented", "{fmt_error}");
+    assert_eq!(check("u32", "PartialEq<u32>")["verdict"], "implemented");
+    assert_eq!(
+        check("crate::UnitStruct", "From<std::io::Error, u8>")["verdict"],
+        "not_implemented"
+    );
+
+    // alloc's docs cannot hold impls of a trait from a crate that depends on it
+    let downstream = check("String", "crate::TestTrait");
+    assert_eq!(downstream["verdict"], "unknown", "{downstream}");
+    assert!(
+        downstream["reason"]
+            .as_str()
+            .unwrap()
+            .contains("impls in test_crate, where `TestTrait` is defined, are not searched"),
+        "{downstream}"
+    );
+
+    let generic = check(
+        "crate::GenericStruct<crate::TestStruct, crate::UnitStruct>",
+        "Send",
+    );
+    assert_eq!(generic["verdict"], "implemented", "{generic}");
+    let bounds = generic["bounds"].as_array().unwrap();
+    assert!(
+        bounds
+            .iter()
+            .any(|bound| bound["type"] == "crate::UnitStruct" && bound["trait"] == "Send"),
+        "{generic}"
+    );
+
+    let open = check("crate::GenericStruct<T, crate::UnitStruct>", "Unpin");
+    assert_eq!(open["verdict"], "unknown", "{open}");
+
+    let text = Implements {
+        type_name: "crate::TestStruct".to_string(),
+        trait_name: "Copy".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        text.starts_with("`crate::TestStruct` does not implement `Copy`"),
+        "{text}"
+    );
+}
+
+#[test]
+fn deref_target_methods_are_listed_and_resolve() {
+    let mut state = create_test_state();
+    let result = GetItem {
+        name: "crate::TestWrapper".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        result.contains("Methods from Deref<Target = TestStruct>:"),
+        "{result}"
+    );
+    assert!(result.contains("fn get_field(&self)"), "{result}");
+
+    let method = GetItem {
+        name: "crate::TestWrapper::get_field".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let method: serde_json::Value = serde_json::from_str(&method).unwrap();
+    assert_eq!(method["kind"], "function", "{method:#}");
+    assert_eq!(
+        method["signature"], "fn get_field(&self) -> &str",
+        "{method:#}"
+    );
+
+    let json = GetItem {
+        name: "crate::TestWrapper".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["deref_methods"][0]["target"], "TestStruct");
+}
+
+#[test]
+fn list_methods_follows_traits_and_deref() {
+    let mut state = create_test_state();
+    let result = ListMethods {
+        name: "crate::TestWrapper".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let methods: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+    let method = |name: &str| {
+        methods
+            .iter()
+            .find(|method| method["name"] == name)
+            .unwrap_or_else(|| panic!("{name} not in {result}"))
+    };
+
+    assert_eq!(method("deref")["receiver"], "&self");
+    assert!(method("deref")["deref_target"].is_null());
+    assert_eq!(method("get_field")["deref_target"], "TestStruct");
+    assert!(method("get_field")["trait"].is_null());
+    assert_eq!(method("increment_count")["receiver"], "&mut self");
+    assert_eq!(method("test_method")["trait"], "test_crate::TestTrait");
+    assert_eq!(method("into")["receiver"], "self");
+    // associated functions without a receiver cannot be called on a value
+    assert!(methods.iter().all(|method| method["name"] != "new"));
+
+    let text = ListMethods {
+        name: "crate::TestStruct".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        text.contains("Inherent methods:\n• &self      fn get_field(&self) -> &str"),
+        "{text}"
+    );
+    assert!(text.contains("Trait `test_crate::TestTrait`:"), "{text}");
+
+    // trait impls whose bounds the type arguments do not meet are left out
+    let mut clone = |type_name: &str| {
+        let result = ListMethods {
+            name: type_name.to_string(),
+            output: Some(OutputFormat::Json),
+            ..Default::default()
+        }
+        .execute(&mut state)
+        .unwrap();
+        let methods: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+        methods
+            .into_iter()
+            .find(|method| method["name"] == "clone" && method["trait"] == "core::clone::Clone")
+    };
+    let cloneable = clone("Vec<crate::TestStruct>").expect("Vec<TestStruct> is Clone");
+    assert!(cloneable["conditional"].is_null(), "{cloneable}");
+    assert_eq!(clone("Vec<crate::UnitStruct>"), None);
+}
+
+#[test]
+fn concrete_generic_arguments_are_substituted() {
+    let mut state = create_test_state();
+    let result = GetItem {
+        name: "crate::GenericStruct<crate::TestStruct, String>".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        result.contains("Type arguments: T = crate::TestStruct, U = String"),
+        "{result}"
+    );
+    assert!(
+        result.contains("fn data(&self) -> &crate::TestStruct"),
+        "{result}"
+    );
+    assert!(
+        result.contains(
+            "fn with_data(self, data: crate::TestStruct) -> crate::GenericStruct<crate::TestStruct, String>"
+        ),
+        "{result}"
+    );
+    // `TestStruct` is not `Copy`
+    assert!(!result.contains("copied_data"), "{result}");
+    assert!(result.contains("1 impl block left out"), "{result}");
+
+    let json = GetItem {
+        name: "crate::GenericStruct<crate::TestStruct, String>".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["type_arguments"][0], "T = crate::TestStruct");
+    assert_eq!(json["omitted_impls"], 1);
+    assert_eq!(
+        json["methods"][1]["signature"],
+        "fn data(&self) -> &crate::TestStruct"
+    );
+
+    let generic = GetItem {
+        name: "crate::GenericStruct".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(generic.contains("fn copied_data(&self) -> T"), "{generic}");
+}
+
+#[test]
+fn type_aliases_show_the_aliased_type() {
+    let mut state = create_test_state();
+    let result = GetItem {
+        name: "crate::TestAlias<crate::TestStruct>".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(
+        result.contains("type TestAlias<T> = GenericStruct<T, String>;"),
+        "{result}"
+    );
+    assert!(
+        result.contains("Aliased type: test_crate::GenericStruct<crate::TestStruct, "),
+        "{result}"
+    );
+    assert!(
+        result.contains("A generic struct for testing multi-paragraph documentation."),
+        "{result}"
+    );
+    assert!(
+        result.contains("fn data(&self) -> &crate::TestStruct"),
+        "{result}"
+    );
+    // `TestStruct` is not `Copy`
+    assert!(!result.contains("copied_data"), "{result}");
+
+    let json = GetItem {
+        name: "crate::TestAlias".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["aliased"]["path"], "test_crate::GenericStruct");
+    assert!(
+        json["methods"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|method| method["signature"] == "fn data(&self) -> &T"),
+        "{json:#}"
+    );
+
+    let method = GetItem {
+        name: "crate::TestAlias::copied_data".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .unwrap();
+    assert!(method.contains("Copy the data out"), "{method}");
+}
@@ -7,6 +7,8 @@ mcplease::tools!(
         "set_working_directory"
     ),
     (GetItem, get_item, "get_item"),
+    (Implements, implements, "implements"),
     (ListCrates, list_crates, "list_crates"),
+    (ListMethods, list_methods, "list_methods"),
     (Search, search, "search")
 );
@@ -1,10 +1,15 @@
 use crate::filter::Filter;
 use crate::format_context::FormatContext;
+use crate::output_format::OutputFormat;
+use crate::pagination::{DEFAULT_MAX_CHARS, Page};
+use crate::request::{NotFoundJson, TypeExpr};
+use crate::resource_uri::ResourceUri;
 use crate::state::RustdocTools;
 use crate::traits::WriteFmt;
 use crate::{request::Request, verbosity::Verbosity};
 use anyhow::Result;
 use clap::{ArgAction, Args};
+use rustdoc_types::ItemEnum;
 use mcplease::{
     traits::{Tool, WithExamples},
     types::Example,
@@ -20,15 +25,25 @@ use strum::VariantArray;
 /// - **Module listing**: `name: "crate::module"` - Lists items in a module  
 /// - **Recursive exploration**: `name: "crate", recursive: true` - Shows all items in module tree
 /// - **Filtered search**: `name: "crate", filter: ["struct", "enum"]` - Shows only specified item types
+/// - **Concrete generics**: `name: "std::option::Option<&str>"` - Shows methods with the type's
+///   parameters filled in, leaving out impl blocks whose bounds the arguments do not meet
+/// - **Type aliases**: `name: "std::io::Result<u8>"` - Shows the aliased type's summary, methods
+///   and trait impls, and resolves paths through the alias like `crate::Db::connect`
 ///
 /// ## Parameter Combinations
 /// - `recursive` works with `filter` to recursively show only filtered item types
 /// - `include_source` adds source code snippets to any item that has them
+/// - `impl_details` expands trait implementations to their headers and associated types
 /// - `verbosity` controls detail level for all output (minimal/brief/full)
+/// - `output: "json"` returns the same information as structured JSON
+/// - `max_items`/`max_chars` limit the size of a response; when output is cut, the response says
+///   what was omitted and gives a `cursor` to pass back for the next page
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Args)]
 #[serde(rename = "get_item")]
 pub struct GetItem {
-    /// The name of the item to show (e.g., "crate::MyStruct", "serde_json::Value", "std::vec::Vec")
+    /// The name of the item to show (e.g., "crate::MyStruct", "serde_json::Value", "std::vec::Vec"),
+    /// optionally with generic arguments (e.g., "std::vec::Vec<u8>"), or a resource URI such as
+    /// "rustdoc://serde_json@1.0.143/Value"
     pub name: String,
 
     /// Whether to include source code snippets (default: false)
@@ -48,10 +63,38 @@ pub struct GetItem {
     #[arg(long, value_enum)]
     pub filter: Option<Vec<Filter>>,
 
+    /// Show each trait implementation's full header, where clause and associated types instead of
+    /// only the trait's name (default: false). To see one implementation in full, name it like
+    /// "crate::MyStruct as Iterator"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub impl_details: Option<bool>,
+
     /// Control documentation verbosity: minimal (structure only), brief (truncated with hints), full (complete)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[arg(long, value_enum)]
     pub verbosity: Option<Verbosity>,
+
+    /// Output format: text (default, human-readable) or json (stable structured schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// Maximum number of entries to show in a module listing (default: no limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub max_items: Option<usize>,
+
+    /// Approximate maximum length of text output in characters (default: 50000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub max_chars: Option<usize>,
+
+    /// Continuation cursor from a previous truncated response, to fetch the next page.
+    /// All other arguments must match the call that produced it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub cursor: Option<String>,
 }
 
 impl GetItem {
@@ -65,6 +108,11 @@ impl GetItem {
         self.recursive.unwrap_or(false)
     }
 
+    /// Get impl_details with default
+    pub(crate) fn impl_details(&self) -> bool {
+        self.impl_details.unwrap_or(false)
+    }
+
     /// Get verbosity with default
     pub(crate) fn verbosity(&self) -> Verbosity {
         self.verbosity.unwrap_or_default()
@@ -74,6 +122,38 @@ impl GetItem {
     pub(crate) fn filters(&self) -> &[Filter] {
         self.filter.as_deref().unwrap_or(Filter::VARIANTS)
     }
+
+    /// The type with generic arguments named, like `std::option::Option<&str>`
+    pub(crate) fn concrete_type(&self) -> Option<TypeExpr> {
+        if self.name.starts_with('<') || self.name.contains(" as ") || ResourceUri::is_uri(&self.name)
+        {
+            return None;
+        }
+        TypeExpr::parse(&self.name)
+            .ok()
+            .filter(|type_| matches!(type_, TypeExpr::Path { args, .. } if !args.is_empty()))
+    }
+
+    /// Get output format with default
+    pub(crate) fn output(&self) -> OutputFormat {
+        self.output.unwrap_or_default()
+    }
+
+    /// Get the page of output to return, validating the cursor against the other arguments
+    pub(crate) fn page(&self) -> Result<Page> {
+        let page = Page::new(
+            self,
+            self.max_items,
+            Some(self.max_chars.unwrap_or(DEFAULT_MAX_CHARS)),
+            self.cursor.as_deref(),
+        )?;
+
+        Ok(if self.output().is_json() {
+            page.without_char_limit()
+        } else {
+            page
+        })
+    }
 }
 
 impl WithExamples for GetItem {
@@ -150,6 +230,45 @@ impl WithExamples for GetItem {
                     ..Default::default()
                 },
             },
+            Example {
+                description: "List a large crate fifty items at a time",
+                item: Self {
+                    name: "std".to_string(),
+                    recursive: Some(true),
+                    max_items: Some(50),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Show the headers and associated types of a type's trait impls",
+                item: Self {
+                    name: "serde_json::Value".to_string(),
+                    impl_details: Some(true),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Show one trait implementation in detail",
+                item: Self {
+                    name: "serde_json::Map as IntoIterator".to_string(),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Show the methods of a generic type for concrete arguments",
+                item: Self {
+                    name: "std::collections::HashMap<String, Vec<u8>>".to_string(),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Get structured JSON for a type",
+                item: Self {
+                    name: "serde_json::Value".to_string(),
+                    output: Some(OutputFormat::Json),
+                    ..Default::default()
+                },
+            },
         ]
     }
 }
@@ -161,23 +280,55 @@ impl Tool<RustdocTools> for GetItem {
 
         let mut suggestions = vec![];
 
-        if let Some(item) = request.resolve_path(&self.name, &mut suggestions) {
-            let context = FormatContext::from_get_item(&self);
+        let (path, item) = if ResourceUri::is_uri(&self.name) {
+            let uri = ResourceUri::parse(&self.name)?;
+            let item = request.resolve_uri(&uri, &mut suggestions)?;
+            (uri.item_path(), item)
+        } else if let Some(TypeExpr::Path { path, .. }) = self.concrete_type() {
+            let item = request.resolve_path(&path, &mut suggestions);
+            (path, item)
+        } else {
+            let item = request.resolve_path(&self.name, &mut suggestions);
+            (self.name.clone(), item)
+        };
+
+        if let Some(item) = item {
+            let context = FormatContext::from_get_item(&self)?
+                .with_uri(request.item_uri(item, &path).map(|uri| uri.to_string()));
 
-            Ok(request.format_item(item, &context))
+            if self.output().is_json() {
+                Ok(serde_json::to_string_pretty(
+                    &request.item_json(item, &context),
+                )?)
+            } else if let ItemEnum::Module(_) = item.inner() {
+                // module listings are paged by entry as they are formatted
+                Ok(request.format_item(item, &context))
+            } else {
+                Ok(context.page().truncate_text(&request.format_item(item, &context)))
+            }
+        } else if let Some(error) = request.load_error(&path) {
+            Err(error.clone().into())
         } else {
-            let mut result = format!("`{}` not found. Did you mean one of these?\n\n", self.name);
-            suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
-            for suggestion in suggestions.into_iter().take(5).filter(|s| s.score() > 0.8) {
-                result.write_fmt(format_args!("• `{}` ", suggestion.path()));
-
-                if let Some(item) = suggestion.item() {
-                    result.write_fmt(format_args!("({:?})\n", item.kind()));
-                } else {
-                    result.push_str("(Crate)\n");
+            request.suggest_across_crates(&path, &mut suggestions);
+            if self.output().is_json() {
+                Ok(serde_json::to_string_pretty(&NotFoundJson::new(
+                    &self.name,
+                    suggestions,
+                ))?)
+            } else {
+                let mut result = format!("`{}` not found. Did you mean one of these?\n\n", self.name);
+                suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
+                for suggestion in suggestions.into_iter().take(5).filter(|s| s.score() > 0.8) {
+                    result.write_fmt(format_args!("• `{}` ", suggestion.path()));
+
+                    if let Some(item) = suggestion.item() {
+                        result.write_fmt(format_args!("({:?})\n", item.kind()));
+                    } else {
+                        result.push_str("(Crate)\n");
+                    }
                 }
+                Ok(result)
             }
-            Ok(result)
         }
     }
 }
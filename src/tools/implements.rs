@@ -0,0 +1,117 @@
+use crate::output_format::OutputFormat;
+use crate::request::{Explanation, Request, TypeExpr, Verdict};
+use crate::state::RustdocTools;
+use crate::traits::WriteFmt;
+use anyhow::Result;
+use clap::Args;
+use mcplease::{
+    traits::{Tool, WithExamples},
+    types::Example,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Check whether a type implements a trait, and why
+///
+/// Looks through the impls documented for the type: direct impls, blanket impls such as
+/// `impl<T: Display> ToString for T`, and auto traits like `Send` and `Sync`. Each impl's bounds
+/// are checked against the type's arguments in turn, so the answer for `Vec<MyType>` shows the
+/// impl that applies and the bounds it needed, or the first bound that is not met.
+///
+/// Type parameters left generic, like the `T` in `Arc<Mutex<T>>`, make the answer depend on
+/// their bounds, which is reported as undetermined.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Args)]
+#[serde(rename = "implements")]
+pub struct Implements {
+    /// The type to check, with any generic arguments (e.g. "crate::MyStruct",
+    /// "std::vec::Vec<crate::MyStruct>", "&str")
+    #[serde(rename = "type")]
+    #[arg(long = "type")]
+    pub type_name: String,
+
+    /// The trait to check for (e.g. "Send", "Clone", "serde::Serialize")
+    #[serde(rename = "trait")]
+    #[arg(long = "trait")]
+    pub trait_name: String,
+
+    /// Output format: text (default, human-readable) or json (stable structured schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+}
+
+impl WithExamples for Implements {
+    fn examples() -> Vec<Example<Self>> {
+        vec![
+            Example {
+                description: "Check whether a type in the current crate is Send",
+                item: Self {
+                    type_name: "crate::MyStruct".to_string(),
+                    trait_name: "Send".to_string(),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Check a generic instantiation against a blanket or bounded impl",
+                item: Self {
+                    type_name: "std::vec::Vec<crate::MyStruct>".to_string(),
+                    trait_name: "Clone".to_string(),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Get the reasoning as structured JSON",
+                item: Self {
+                    type_name: "std::sync::Arc<crate::MyStruct>".to_string(),
+                    trait_name: "Sync".to_string(),
+                    output: Some(OutputFormat::Json),
+                },
+            },
+        ]
+    }
+}
+
+impl Tool<RustdocTools> for Implements {
+    fn execute(self, tools: &mut RustdocTools) -> Result<String> {
+        let project = tools.project_context(None)?;
+        let request = Request::new(project);
+        let type_ = TypeExpr::parse(&self.type_name)?;
+        let trait_ = TypeExpr::parse(&self.trait_name)?;
+        let explanation = request.explain_implements(&type_, &trait_);
+
+        if self.output.unwrap_or_default().is_json() {
+            return Ok(serde_json::to_string_pretty(&explanation)?);
+        }
+
+        let (type_, trait_) = (explanation.type_(), explanation.trait_());
+        let mut result = match explanation.verdict() {
+            Verdict::Implemented => format!("`{type_}` implements `{trait_}`\n\n"),
+            Verdict::NotImplemented => format!("`{type_}` does not implement `{trait_}`\n\n"),
+            Verdict::Unknown => {
+                format!("Could not determine whether `{type_}` implements `{trait_}`\n\n")
+            }
+        };
+        format_explanation(&explanation, 0, &mut result);
+        Ok(result)
+    }
+}
+
+/// One line per check, with the bounds it depends on indented below it
+fn format_explanation(explanation: &Explanation, depth: usize, result: &mut String) {
+    let mark = match explanation.verdict() {
+        Verdict::Implemented => "✓",
+        Verdict::NotImplemented => "✗",
+        Verdict::Unknown => "?",
+    };
+    result.write_fmt(format_args!(
+        "{:indent$}{mark} `{}: {}` {}\n",
+        "",
+        explanation.type_(),
+        explanation.trait_(),
+        explanation.reason(),
+        indent = depth * 2
+    ));
+    for bound in explanation.bounds() {
+        format_explanation(bound, depth + 1, result);
+    }
+}
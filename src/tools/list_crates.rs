@@ -1,4 +1,4 @@
-use crate::{state::RustdocTools, traits::WriteFmt};
+use crate::{output_format::OutputFormat, state::RustdocTools, traits::WriteFmt};
 use anyhow::Result;
 use clap::Args;
 use mcplease::traits::{Tool, WithExamples};
@@ -13,6 +13,12 @@ pub struct ListCrates {
     /// Optional workspace member to scope dependencies to
     #[arg(long)]
     pub workspace_member: Option<String>,
+
+    /// Output format: text (default, human-readable) or json (stable structured schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+
     #[serde(skip)]
     pub for_schemars: (),
 }
@@ -30,6 +36,13 @@ impl Tool<RustdocTools> for ListCrates {
     fn execute(self, state: &mut RustdocTools) -> Result<String> {
         let project = state.project_context(None)?;
 
+        if self.output.unwrap_or_default().is_json() {
+            let crates = project
+                .crate_info(self.workspace_member.as_deref())
+                .collect::<Vec<_>>();
+            return Ok(serde_json::to_string_pretty(&crates)?);
+        }
+
         // Determine if we're showing a member-scoped view (either via parameter or working directory)
         let is_scoped_view =
             self.workspace_member.is_some() || project.detect_subcrate_context().is_some();
@@ -0,0 +1,120 @@
+use crate::output_format::OutputFormat;
+use crate::request::{CallableMethod, Request, TypeExpr};
+use crate::state::RustdocTools;
+use crate::traits::WriteFmt;
+use anyhow::{Result, anyhow};
+use clap::Args;
+use mcplease::{
+    traits::{Tool, WithExamples},
+    types::Example,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// List every method that can be called on a value of a type, as method lookup finds them
+///
+/// Includes inherent methods, methods of every implemented trait (with the trait's path, which
+/// must be imported to call them), methods reachable through `Deref`, and methods of blanket
+/// impls. Each method shows how it takes its receiver: `self`, `&self` or `&mut self`.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Args)]
+#[serde(rename = "list_methods")]
+pub struct ListMethods {
+    /// The type whose methods to list, optionally with generic arguments (e.g. "crate::MyStruct",
+    /// "std::string::String", "std::vec::Vec<crate::MyStruct>")
+    pub name: String,
+
+    /// Crates whose blanket impls to check against the type as well, for traits like
+    /// `itertools::Itertools` that any iterator implements (default: none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub blanket_crates: Option<Vec<String>>,
+
+    /// Output format: text (default, human-readable) or json (stable structured schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+}
+
+impl WithExamples for ListMethods {
+    fn examples() -> Vec<Example<Self>> {
+        vec![
+            Example {
+                description: "List the methods callable on a type in the current crate",
+                item: Self {
+                    name: "crate::MyStruct".to_string(),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Include the methods itertools adds to an iterator",
+                item: Self {
+                    name: "std::vec::IntoIter<u8>".to_string(),
+                    blanket_crates: Some(vec!["itertools".to_string()]),
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Get the methods as structured JSON",
+                item: Self {
+                    name: "std::string::String".to_string(),
+                    output: Some(OutputFormat::Json),
+                    ..Default::default()
+                },
+            },
+        ]
+    }
+}
+
+impl Tool<RustdocTools> for ListMethods {
+    fn execute(self, tools: &mut RustdocTools) -> Result<String> {
+        let project = tools.project_context(None)?;
+        let request = Request::new(project);
+        let type_ = TypeExpr::parse(&self.name)?;
+        let methods = request
+            .callable_methods(&type_, self.blanket_crates.as_deref().unwrap_or_default())
+            .ok_or_else(|| anyhow!("`{}` not found", self.name))?;
+
+        if self.output.unwrap_or_default().is_json() {
+            return Ok(serde_json::to_string_pretty(&methods)?);
+        }
+
+        let mut result = format!("Methods callable on `{type_}`:\n");
+        let mut group = None;
+        for method in &methods {
+            let method_group = (method.deref_target(), method.trait_path());
+            if group != Some(method_group) {
+                group = Some(method_group);
+                result.push('\n');
+                result.push_str(&group_title(method));
+                result.push_str(":\n");
+            }
+            result.write_fmt(format_args!(
+                "• {:<10} {}\n",
+                method.receiver(),
+                method.signature()
+            ));
+        }
+        if methods.is_empty() {
+            result.push_str("\nNone found.\n");
+        }
+
+        Ok(result)
+    }
+}
+
+/// Where the methods that follow come from, e.g. "Trait `core::clone::Clone`"
+fn group_title(method: &CallableMethod<'_>) -> String {
+    let mut title = match method.deref_target() {
+        Some(target) => format!("Through Deref<Target = {target}>, "),
+        None => String::new(),
+    };
+    match method.trait_path() {
+        Some(trait_path) => title.write_fmt(format_args!("trait `{trait_path}`")),
+        None => title.push_str("inherent methods"),
+    }
+    if method.conditional() {
+        title.push_str(" (if the impl's bounds hold for the type's arguments)");
+    }
+    // capitalize the first letter, as titles do
+    title[..1].to_uppercase() + &title[1..]
+}
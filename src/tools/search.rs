@@ -1,28 +1,111 @@
+use crate::filter::Filter;
 use crate::format_context::FormatContext;
 use crate::indent::Indent;
-use crate::indexer::SearchIndex;
-use crate::request::Request;
+use crate::indexer::{FieldWeights, SearchIndex};
+use crate::output_format::OutputFormat;
+use crate::pagination::{DEFAULT_MAX_CHARS, Page};
+use crate::request::{NotFoundJson, Request};
+use crate::rustdoc::CrateType;
 use crate::state::RustdocTools;
 use crate::traits::WriteFmt;
-use anyhow::Result;
+use anyhow::{Result, bail};
 use mcplease::traits::{Tool, WithExamples};
 use mcplease::types::Example;
+use rustdoc_types::ItemKind;
 use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 
-/// Search for items within a specific crate
-#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema, clap::Args)]
+/// Search for items within a specific crate, or across the workspace and its dependencies
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema, clap::Args)]
 #[serde(rename = "search")]
 pub struct Search {
-    /// The crate to search within. Use `crate` for the current crate.
-    pub crate_name: String,
+    /// The crate to search within. Use `crate` for the current crate. Omit to search all
+    /// workspace members and their (non-dev) dependencies at once, among those whose docs are
+    /// built; name a crate to build its docs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crate_name: Option<String>,
+
+    /// When searching all crates, also search the standard library (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub include_std: Option<bool>,
 
-    /// The search query to look for. Individual terms will be combined additively.
+    /// The search query to look for. Individual terms will be combined additively. Terms also
+    /// match as prefixes and with small typos, ranked below exact matches.
     pub query: String,
 
+    /// Only show items of these kinds. Supports the same values as `get_item`'s `filter`.
+    /// Default: all item types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, value_enum)]
+    pub filter: Option<Vec<Filter>>,
+
+    /// Only show items under this module path (e.g. "tokio::sync"). When `crate_name` is
+    /// omitted, only the crate this path starts with is searched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub path_prefix: Option<String>,
+
+    /// Leave out items re-exported from other crates, so each is only found where it is defined
+    /// (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub exclude_reexports: Option<bool>,
+
     /// Maximum number of results to return (default: 10)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[arg(short, long)]
     pub limit: Option<usize>,
+
+    /// Output format: text (default, human-readable) or json (stable structured schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// Maximum number of results to show per page (default: all results up to `limit`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub max_items: Option<usize>,
+
+    /// Approximate maximum length of text output in characters (default: 50000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub max_chars: Option<usize>,
+
+    /// Continuation cursor from a previous truncated response, to fetch the next page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[arg(long)]
+    pub cursor: Option<String>,
+}
+
+/// JSON response for a search
+#[derive(Debug, Serialize)]
+struct SearchJson<'a> {
+    query: &'a str,
+    /// The crate searched, when only one was
+    #[serde(rename = "crate", skip_serializing_if = "Option::is_none")]
+    crate_name: Option<&'a str>,
+    results: Vec<SearchResult>,
+    /// Crates that were not searched because their docs are not built, or failed to load
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    skipped: Vec<String>,
+    /// Number of results left off this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    omitted: Option<usize>,
+    /// Pass back as `cursor` to fetch the next page of results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchResult {
+    #[serde(rename = "crate")]
+    crate_name: String,
+    path: String,
+    kind: ItemKind,
+    /// Percentage of the total score of all matches
+    score: f32,
+    summary: Option<String>,
 }
 
 impl WithExamples for Search {
@@ -31,17 +114,26 @@ impl WithExamples for Search {
             Example {
                 description: "Search for 'Error' in std crate",
                 item: Self {
-                    crate_name: "std".into(),
+                    crate_name: Some("std".into()),
                     query: "Error".into(),
                     limit: Some(5),
+                    ..Default::default()
                 },
             },
             Example {
                 description: "Search for 'iterator items' in current crate",
                 item: Self {
-                    crate_name: "crate".into(),
+                    crate_name: Some("crate".into()),
                     query: "iterator items".into(),
                     limit: None,
+                    ..Default::default()
+                },
+            },
+            Example {
+                description: "Find which dependency provides retry helpers",
+                item: Self {
+                    query: "retry backoff".into(),
+                    ..Default::default()
                 },
             },
         ]
@@ -51,52 +143,147 @@ impl WithExamples for Search {
 impl Tool<RustdocTools> for Search {
     fn execute(self, state: &mut RustdocTools) -> Result<String> {
         let project = state.project_context(None)?;
+        let output = self.output.unwrap_or_default();
+        let mut page = Page::new(
+            &self,
+            self.max_items,
+            Some(self.max_chars.unwrap_or(DEFAULT_MAX_CHARS)),
+            self.cursor.as_deref(),
+        )?;
+        if output.is_json() {
+            page = page.without_char_limit();
+        }
+
+        // A path prefix names the crate to search when none is given
+        let (prefix_crate, prefix_segments) = match &self.path_prefix {
+            Some(prefix) => {
+                let mut segments = prefix.split("::").filter(|segment| !segment.is_empty());
+                (segments.next(), segments.collect::<Vec<_>>())
+            }
+            None => (None, vec![]),
+        };
+        if let (Some(crate_name), Some(prefix_crate)) = (&self.crate_name, prefix_crate)
+            && project.normalize_crate_name(crate_name)
+                != project.normalize_crate_name(prefix_crate)
+        {
+            bail!("`path_prefix` must be within `{crate_name}`, but starts with `{prefix_crate}`");
+        }
+        let crate_name = self.crate_name.as_deref().or(prefix_crate);
+
+        let weights = FieldWeights::configured()?;
+        let request = Request::new(Rc::clone(&project));
+        let mut skipped = vec![];
+        let indexes = match crate_name {
+            Some(crate_name) => match SearchIndex::load_or_build(&request, crate_name, weights) {
+                Ok(index) => vec![index],
+                Err(mut suggestions) => {
+                    if let Some(error) = request.load_error(crate_name) {
+                        return Err(error.clone().into());
+                    }
+                    if output.is_json() {
+                        return Ok(serde_json::to_string_pretty(&NotFoundJson::new(
+                            crate_name,
+                            suggestions,
+                        ))?);
+                    }
+                    let mut result =
+                        format!("`{crate_name}` not found. Did you mean one of these?\n\n");
+                    suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
+                    for suggestion in suggestions.into_iter().take(5).filter(|s| s.score() > 0.8) {
+                        result.write_fmt(format_args!("• `{}` ", suggestion.path()));
 
-        let request = Request::new(project);
-        let index = match SearchIndex::load_or_build(&request, &self.crate_name) {
-            Ok(index) => index,
-            Err(mut suggestions) => {
-                let mut result = format!(
-                    "`{}` not found. Did you mean one of these?\n\n",
-                    &self.crate_name
-                );
-                suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
-                for suggestion in suggestions.into_iter().take(5).filter(|s| s.score() > 0.8) {
-                    result.write_fmt(format_args!("• `{}` ", suggestion.path()));
-
-                    if let Some(item) = suggestion.item() {
-                        result.write_fmt(format_args!("({:?})\n", item.kind()));
-                    } else {
-                        result.push_str("(Crate)\n");
+                        if let Some(item) = suggestion.item() {
+                            result.write_fmt(format_args!("({:?})\n", item.kind()));
+                        } else {
+                            result.push_str("(Crate)\n");
+                        }
                     }
+                    return Ok(result);
                 }
-                return Ok(result);
+            },
+
+            // Searching everything does not build docs, which on a cold target directory would
+            // run `cargo doc` for the whole dependency graph. Workspace crates built before are
+            // rebuilt once edited, as when they are searched by name.
+            None => {
+                let include_std = self.include_std.unwrap_or(false);
+                let mut indexes = vec![];
+                for crate_info in project.crate_info(None) {
+                    let name = crate_info.name();
+                    let searched = match crate_info.crate_type() {
+                        CrateType::Workspace => true,
+                        CrateType::Library => !crate_info.is_dev_dep(),
+                        // std re-exports core and alloc, so searching those would duplicate results
+                        CrateType::Rust => include_std && name == "std",
+                    };
+                    if !searched {
+                        continue;
+                    }
+
+                    let built_before = project
+                        .normalize_crate_name(name)
+                        .and_then(|crate_name| project.resolve_json_path(crate_name))
+                        .is_some_and(|(json_path, _)| json_path.exists());
+                    let index = match crate_info.crate_type() {
+                        CrateType::Workspace if built_before => {
+                            SearchIndex::load_or_build(&request, name, weights).ok()
+                        }
+                        CrateType::Workspace => None,
+                        _ => SearchIndex::load_built(&request, name, weights),
+                    };
+                    match index {
+                        Some(index) => indexes.push(index),
+                        None => skipped.push(name.to_string()),
+                    }
+                }
+                indexes
             }
         };
 
-        // Perform search
+        // Perform search, merging results from every crate into one ranking
         let limit = self.limit.unwrap_or(10);
-        let results = index.search(&self.query);
+        let mut results = indexes
+            .iter()
+            .flat_map(|index| {
+                index
+                    .search(&self.query)
+                    .into_iter()
+                    .map(move |(id, score)| (index, id, score))
+            })
+            .collect::<Vec<_>>();
+        results.sort_by(|(_, _, a), (_, _, b)| b.total_cmp(a));
 
-        // Format results
-        let mut output = String::new();
-        output.write_fmt(format_args!(
-            "Search results for '{}' in crate '{}':\n\n",
-            self.query,
-            index.crate_name()
-        ));
+        // Filter before ranking cutoffs, so that they only compare results the caller wants
+        let exclude_reexports = self.exclude_reexports.unwrap_or(false);
+        if self.filter.is_some() || !prefix_segments.is_empty() || exclude_reexports {
+            results.retain(|(index, id, _)| {
+                let Some((item, path)) = request.get_item_from_id_path(index.crate_name(), id)
+                else {
+                    return false;
+                };
+                if exclude_reexports
+                    && (item.crate_docs().name() != index.crate_name()
+                        || request.is_reexport(index.crate_name(), id))
+                {
+                    return false;
+                }
+                self.filter.as_ref().is_none_or(|filters| {
+                    filters.iter().any(|filter| filter.matches_kind(item.kind()))
+                }) && path.get(1..).is_some_and(|path| path.starts_with(&prefix_segments))
+            });
+        }
 
-        if results.is_empty() {
-            output.push_str("No results found.\n");
-        } else {
-            let total_score: f32 = results.iter().map(|(_, score)| score).sum();
+        let mut hits = vec![];
+
+        if !results.is_empty() {
+            let total_score: f32 = results.iter().map(|(_, _, score)| score).sum();
             let mut cumulative_score = 0.0;
             let min_results = 1;
 
-            let top_score = results.first().map(|(_, score)| *score).unwrap_or(0.0);
+            let top_score = results.first().map(|(_, _, score)| *score).unwrap_or(0.0);
             let mut prev_score = top_score;
 
-            for (i, (id, score)) in results.into_iter().take(limit).enumerate() {
+            for (i, (index, id, score)) in results.into_iter().take(limit).enumerate() {
                 if i >= min_results
                     && (score / top_score < 0.05
                         || score / prev_score < 0.5
@@ -105,24 +292,100 @@ impl Tool<RustdocTools> for Search {
                     break;
                 }
 
-                if let Some((item, path)) = request.get_item_from_id_path(&self.crate_name, id) {
+                if let Some((item, path)) = request.get_item_from_id_path(index.crate_name(), id)
+                {
                     cumulative_score += score;
                     prev_score = score;
-                    let path = path.join("::");
-                    let normalized_score = 100.0 * score / total_score;
-                    output.write_fmt(format_args!(
-                        "• {path} ({:?}) - score: {normalized_score:.0}\n",
-                        item.kind()
-                    ));
-
-                    if let Some(docs) = request.docs_to_show(item, true, &FormatContext::default())
-                    {
-                        output.write_fmt(format_args!("{}", Indent::new(&docs, 4)));
-                    }
+                    hits.push(SearchResult {
+                        crate_name: index.crate_name().to_string(),
+                        path: path.join("::"),
+                        kind: item.kind(),
+                        score: 100.0 * score / total_score,
+                        summary: request.docs_to_show(item, true, &FormatContext::default()),
+                    });
                 }
             }
         }
 
+        if output.is_json() {
+            let total = hits.len();
+            let shown = page.select(std::iter::repeat_n(0, total));
+            let (omitted, next_cursor) = if shown.end < total {
+                (Some(total - shown.end), Some(page.cursor_at(shown.end)))
+            } else {
+                (None, None)
+            };
+
+            return Ok(serde_json::to_string_pretty(&SearchJson {
+                query: &self.query,
+                crate_name: crate_name
+                    .is_some()
+                    .then(|| indexes.first().map(SearchIndex::crate_name))
+                    .flatten(),
+                results: hits.drain(shown).collect(),
+                skipped,
+                omitted,
+                next_cursor,
+            })?);
+        }
+
+        // Format results
+        let mut output = String::new();
+        match (crate_name, indexes.first()) {
+            (Some(_), Some(index)) => output.write_fmt(format_args!(
+                "Search results for '{}' in crate '{}':\n\n",
+                self.query,
+                index.crate_name()
+            )),
+            _ => output.write_fmt(format_args!(
+                "Search results for '{}' across {} crate{}:\n\n",
+                self.query,
+                indexes.len(),
+                if indexes.len() == 1 { "" } else { "s" }
+            )),
+        }
+
+        if hits.is_empty() {
+            output.push_str("No results found.\n");
+        }
+
+        let entries = hits
+            .into_iter()
+            .map(
+                |SearchResult {
+                     path,
+                     kind,
+                     score,
+                     summary,
+                     ..
+                 }| {
+                    let mut entry = format!("• {path} ({kind:?}) - score: {score:.0}\n");
+
+                    if let Some(docs) = summary {
+                        entry.write_fmt(format_args!("{}", Indent::new(&docs, 4)));
+                    }
+                    entry
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let shown = page.select(entries.iter().map(String::len));
+        for entry in &entries[shown.clone()] {
+            output.push_str(entry);
+        }
+
+        if let Some(note) = page.omission_note(&shown, entries.len(), "results", None) {
+            output.push_str(&note);
+        }
+
+        if !skipped.is_empty() {
+            output.write_fmt(format_args!(
+                "\nNot searched, as their docs are not built or could not be loaded: {}\n\
+                 Search one by passing it as `crate_name`, which builds its docs.\n",
+                skipped.join(", ")
+            ));
+        }
+
         Ok(output)
     }
 }
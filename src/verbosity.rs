@@ -3,10 +3,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Controls the verbosity level of documentation display
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ValueEnum)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ValueEnum, Default,
+)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum Verbosity {
     Minimal,
+    #[default]
     Brief,
     Full,
 }
@@ -16,9 +19,3 @@ impl Verbosity {
         matches!(self, Self::Full)
     }
 }
-
-impl Default for Verbosity {
-    fn default() -> Self {
-        Self::Brief
-    }
-}
@@ -1,6 +1,8 @@
 use crate::{
+    output_format::OutputFormat,
+    request::Request,
     state::RustdocTools,
-    tools::{GetItem, ListCrates, SetWorkingDirectory},
+    tools::{GetItem, ListCrates, Search, SetWorkingDirectory},
 };
 use mcplease::traits::Tool;
 use std::path::PathBuf;
@@ -57,7 +59,7 @@ fn test_workspace_member_parameter_crate_a() {
 
     let result = ListCrates {
         workspace_member: Some("crate-a".to_string()),
-        for_schemars: (),
+        ..Default::default()
     }
     .execute(&mut state)
     .unwrap();
@@ -86,7 +88,7 @@ fn test_workspace_member_parameter_crate_b() {
 
     let result = ListCrates {
         workspace_member: Some("crate-b".to_string()),
-        for_schemars: (),
+        ..Default::default()
     }
     .execute(&mut state)
     .unwrap();
@@ -116,7 +118,7 @@ fn test_workspace_member_parameter_vs_working_directory() {
 
     let result = ListCrates {
         workspace_member: Some("crate-b".to_string()), // But request crate-b scope
-        for_schemars: (),
+        ..Default::default()
     }
     .execute(&mut state)
     .unwrap();
@@ -287,6 +289,47 @@ fn test_workspace_get_crate_b() {
     insta::assert_snapshot!(result);
 }
 
+#[test]
+fn test_workspace_reexport_from_renamed_crate() {
+    let mut state = create_workspace_test_state();
+
+    // `pub use renamed_a::CrateAStruct as ReexportedStruct`, where `renamed_a` is `crate_a`
+    // under another name, so only crate-b's `paths` and `external_crates` say where it is
+    let result = GetItem {
+        name: "crate-b::ReexportedStruct".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .expect("Tool execution failed");
+    assert!(
+        result.contains("Defined at: crate_a::CrateAStruct"),
+        "{result}"
+    );
+    assert!(result.contains("pub value: i32"), "{result}");
+
+    let listing = GetItem {
+        name: "crate-b".to_string(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .expect("Tool execution failed");
+    assert!(listing.contains("ReexportedStruct"), "{listing}");
+}
+
+#[test]
+fn test_crate_loaded_by_name_and_by_reference_is_parsed_once() {
+    let mut state = create_workspace_test_state();
+    let request = Request::new(state.project_context(None).unwrap());
+
+    let reexported = request
+        .resolve_path("crate-b::ReexportedStruct", &mut vec![])
+        .unwrap();
+    let direct = request
+        .resolve_path("crate-a::CrateAStruct", &mut vec![])
+        .unwrap();
+    assert!(std::ptr::eq(reexported.crate_docs(), direct.crate_docs()));
+}
+
 #[test]
 fn test_workspace_access_dependency() {
     let mut state = create_workspace_test_state();
@@ -304,3 +347,56 @@ fn test_workspace_access_dependency() {
 
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn test_search_across_workspace_crates() {
+    let mut state = create_workspace_test_state();
+
+    for (query, crate_name, path) in [
+        ("CrateBProcessor", "crate-b", "crate-b::CrateBProcessor"),
+        ("CrateAStruct", "crate-a", "crate-a::CrateAStruct"),
+    ] {
+        let result = Search {
+            query: query.into(),
+            output: Some(OutputFormat::Json),
+            ..Default::default()
+        }
+        .execute(&mut state)
+        .expect("Tool execution failed");
+
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert!(json.get("crate").is_none());
+        assert_eq!(json["results"][0]["crate"], crate_name);
+        assert_eq!(json["results"][0]["path"], path);
+    }
+
+    let result = Search {
+        query: "CrateBProcessor".into(),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .expect("Tool execution failed");
+    assert!(result.starts_with("Search results for 'CrateBProcessor' across "));
+}
+
+#[test]
+fn test_not_found_suggests_dependency_items() {
+    let mut state = create_workspace_test_state();
+
+    let result = GetItem {
+        name: "Regex".to_string(),
+        output: Some(OutputFormat::Json),
+        ..Default::default()
+    }
+    .execute(&mut state)
+    .expect("Tool execution failed");
+
+    let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let paths = json["suggestions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|suggestion| suggestion["path"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert!(paths.contains(&"regex::Regex"), "{paths:?}");
+}
@@ -0,0 +1,6 @@
+//! A crate with a deliberate resolution error, so that `cargo doc` fails
+
+/// This function's return type does not exist
+pub fn broken() -> DoesNotExist {
+    unimplemented!()
+}
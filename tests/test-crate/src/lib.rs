@@ -193,6 +193,12 @@ pub static TEST_STATIC: &str = "hello world";
 /// A unit struct for testing
 pub struct UnitStruct;
 
+impl From<std::io::Error> for UnitStruct {
+    fn from(_: std::io::Error) -> Self {
+        UnitStruct
+    }
+}
+
 /// A tuple struct for testing
 pub struct TupleStruct(
     /// It's probably uncommon to add documentation for a tuple struct field
@@ -239,8 +245,53 @@ where
         U: Into<T>;
 }
 
+/// A wrapper that derefs to the struct it wraps
+pub struct TestWrapper(TestStruct);
+
+impl std::ops::Deref for TestWrapper {
+    type Target = TestStruct;
+
+    fn deref(&self) -> &TestStruct {
+        &self.0
+    }
+}
+
+/// Methods for any `GenericStruct`
+impl<T, U> GenericStruct<T, U>
+where
+    T: Clone + Send,
+    U: std::fmt::Display,
+{
+    /// Replace the data
+    pub fn with_data(mut self, data: T) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Get a reference to the data
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+}
+
+/// Methods for a `GenericStruct` whose data is `Copy`
+impl<T, U> GenericStruct<T, U>
+where
+    T: Clone + Send + Copy,
+    U: std::fmt::Display,
+{
+    /// Copy the data out
+    pub fn copied_data(&self) -> T {
+        self.data
+    }
+}
+
+/// A `GenericStruct` with a string for metadata
+pub type TestAlias<T> = GenericStruct<T, String>;
+
 pub use std::vec::Vec;
 pub use submodule::*;
 pub mod reexport_mod {
     pub use super::submodule::*;
 }
+
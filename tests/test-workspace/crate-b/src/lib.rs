@@ -19,4 +19,9 @@ impl CrateBProcessor {
     pub fn count(&self) -> usize {
         self.data.len()
     }
-}
\ No newline at end of file
+}
+
+extern crate crate_a as renamed_a;
+
+/// `CrateAStruct`, re-exported through a renamed crate
+pub use renamed_a::CrateAStruct as ReexportedStruct;